@@ -15,15 +15,19 @@ fn main() {
         let name = path.file_name().unwrap().to_str().unwrap();
         let source = fs::read_to_string(&path).unwrap();
 
-        match lemon_core::interpret(&source) {
-            Ok(statements) => {
+        match lemon_core::parse(&source) {
+            Ok(_statements) => {
                 println!("[PASS] {} → AST generado correctamente\n", name);
                 // Aquí puedes imprimir el AST si quieres
                 // println!("{:#?}", statements);
             }
 
-            Err(e) => {
-                println!("[FAIL] {} → Error:\n{}\n", name, e);
+            Err(diagnostics) => {
+                let map = lemon_core::span::SourceMap::new(name, &source);
+                println!("[FAIL] {} → {} error(es):\n", name, diagnostics.len());
+                for diagnostic in &diagnostics {
+                    println!("{}", diagnostic.render(&map));
+                }
             }
         }
     }
@@ -1,26 +1,222 @@
 use std::env;
 use std::fs;
-use lemon_core::interpret;
+use lemon_core::{check_with_diagnostics, parse_with_diagnostics, set_locale, Diagnostic, Locale, Value};
+use lemon_core::eval::Interpreter;
+use lemon_core::ast::tree;
+use lemon_core::diagnostic::{codes, Severity};
+use lemon_core::format::{convert_keywords, migrate_blocks};
+use lemon_core::i18n::tr;
+use lemon_core::lexer::{keyword_pragma, tokenize};
+use lemon_core::span::SourceMap;
+use lemon_core::token::print_tokens_by_line;
+use serde::Serialize;
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    // El idioma se toma de `YUKA_LANG`; `--lang` tiene prioridad
+    if let Some(locale) = Locale::from_env() {
+        set_locale(locale);
+    }
 
-    if args.len() != 2 {
-        eprintln!("Uso: yuka <archivo.yuka>");
+    let mut filename = None;
+    let mut command = Command::Run;
+    let mut keywords = None;      // `--keywords`: idioma de las palabras clave para `fmt`
+    let mut output = Output::Text; // `--format`: salida de `tokens` y `ast`
+    let mut deny_old = false;      // `--deny-old`: usar una declaración `old` es un error
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if let Some(tag) = flag_value(&arg, "--lang", &mut args) {
+            set_locale(parse_locale(&tag));
+        } else if let Some(tag) = flag_value(&arg, "--keywords", &mut args) {
+            keywords = Some(parse_locale(&tag));
+        } else if let Some(name) = flag_value(&arg, "--format", &mut args) {
+            output = Output::parse(&name).unwrap_or_else(|| {
+                eprintln!("{}", tr("cli.unknown_format", &[&name]));
+                std::process::exit(1);
+            });
+        } else if arg == "--deny-old" {
+            deny_old = true;
+        } else if let Some(cmd) = Command::parse(&arg).filter(|_| command == Command::Run && filename.is_none()) {
+            command = cmd;
+        } else if filename.is_none() {
+            filename = Some(arg);
+        } else {
+            usage();
+        }
+    }
+
+    let Some(filename) = filename else { usage() };
+    let input = fs::read_to_string(&filename).unwrap_or_else(|e| {
+        eprintln!("{}", tr("cli.read_error", &[&filename, &e]));
         std::process::exit(1);
+    });
+
+    match command {
+        Command::Run => {
+            // Las advertencias se muestran antes de ejecutar; los errores lo impiden
+            let (statements, resolution, mut diagnostics) = check_with_diagnostics(&input);
+            if deny_old {
+                deny_deprecated(&mut diagnostics);
+            }
+            report_errors(&filename, &input, &diagnostics);
+            match Interpreter::new(resolution).run(&statements) {
+                Ok(Value::None) => {} // El programa no produjo un valor final
+                Ok(result) => {
+                    println!("{}", result);
+                }
+                Err(diagnostic) => report(&filename, &input, &[diagnostic]),
+            }
+        }
+        Command::Check => {
+            let (_, _, mut diagnostics) = check_with_diagnostics(&input);
+            if deny_old {
+                deny_deprecated(&mut diagnostics);
+            }
+            report_errors(&filename, &input, &diagnostics);
+        }
+        Command::Format => {
+            // Sin `--keywords` se conserva el idioma del archivo y solo se normaliza
+            let target = keywords
+                .or_else(|| keyword_pragma(&input).map(|(locale, _)| locale))
+                .unwrap_or(Locale::English);
+            match convert_keywords(&input, target) {
+                Ok(output) => print!("{}", output),
+                Err(diagnostics) => report(&filename, &input, &diagnostics),
+            }
+        }
+        Command::Migrate => match migrate_blocks(&input) {
+            Ok(output) => print!("{}", output),
+            Err(diagnostics) => report(&filename, &input, &diagnostics),
+        },
+        Command::Tokens => {
+            let (tokens, diagnostics) = tokenize(&input);
+            match output {
+                Output::Text => print_tokens_by_line(&tokens),
+                Output::Json => print_json(&tokens),
+                Output::JsonLines => tokens.iter().for_each(print_json_line),
+            }
+            report_errors(&filename, &input, &diagnostics);
+        }
+        Command::Ast => {
+            // Con errores se muestra el AST parcial y después los errores
+            let (statements, diagnostics) = parse_with_diagnostics(&input);
+            match output {
+                Output::Text => print!("{}", tree::render(&statements)),
+                Output::Json => print_json(&statements),
+                Output::JsonLines => statements.iter().for_each(print_json_line),
+            }
+            report_errors(&filename, &input, &diagnostics);
+        }
     }
+}
 
-    let filename = &args[1];
-    let input = fs::read_to_string(filename)
-        .expect("No se pudo leer el archivo de entrada");
+/// Qué hacer con el archivo.
+#[derive(Clone, Copy, PartialEq)]
+enum Command {
+    /// Ejecutarlo (sin subcomando).
+    Run,
+    /// `yuka check`: comprobar la sintaxis y los tipos sin ejecutarlo.
+    Check,
+    /// `yuka fmt`: reescribir las palabras clave en otro idioma.
+    Format,
+    /// `yuka migrate`: pasar los bloques `{ }` al estilo con `end`.
+    Migrate,
+    /// `yuka tokens`: mostrar los tokens del archivo.
+    Tokens,
+    /// `yuka ast`: mostrar el árbol de sintaxis.
+    Ast,
+}
 
-    match interpret(&input) {
-        Ok(result) => {
-            println!("{:?}", result); // Aquí puedes hacer `.trim()` si es necesario
+impl Command {
+    fn parse(arg: &str) -> Option<Self> {
+        match arg {
+            "check" => Some(Command::Check),
+            "fmt" => Some(Command::Format),
+            "migrate" => Some(Command::Migrate),
+            "tokens" => Some(Command::Tokens),
+            "ast" => Some(Command::Ast),
+            _ => None,
         }
-        Err(e) => {
-            eprintln!("❌ Error: {}", e);
-            std::process::exit(1);
+    }
+}
+
+/// Formato de salida de `tokens` y `ast`.
+#[derive(Clone, Copy, PartialEq)]
+enum Output {
+    /// Para leer en la consola: tabla de tokens por línea o árbol del AST.
+    Text,
+    /// Un único documento JSON con la lista completa.
+    Json,
+    /// Un objeto JSON por línea (un token o una instrucción).
+    JsonLines,
+}
+
+impl Output {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "table" | "tree" | "text" => Some(Output::Text),
+            "json" => Some(Output::Json),
+            "jsonl" => Some(Output::JsonLines),
+            _ => None,
         }
     }
 }
+
+/// Imprime `value` como JSON con sangría.
+fn print_json(value: &impl Serialize) {
+    println!("{}", serde_json::to_string_pretty(value).expect("el AST y los tokens siempre se serializan"));
+}
+
+/// Imprime `value` como JSON en una sola línea.
+fn print_json_line(value: &impl Serialize) {
+    println!("{}", serde_json::to_string(value).expect("el AST y los tokens siempre se serializan"));
+}
+
+/// Valor de una opción `--nombre valor` o `--nombre=valor`, si `arg` lo es.
+fn flag_value(arg: &str, name: &str, args: &mut impl Iterator<Item = String>) -> Option<String> {
+    if arg == name {
+        return Some(args.next().unwrap_or_default());
+    }
+    arg.strip_prefix(name)?.strip_prefix('=').map(str::to_string)
+}
+
+/// Interpreta una etiqueta de idioma o termina con error.
+fn parse_locale(tag: &str) -> Locale {
+    Locale::from_tag(tag).unwrap_or_else(|| {
+        eprintln!("{}", tr("cli.unknown_lang", &[&tag]));
+        std::process::exit(1);
+    })
+}
+
+/// Muestra los diagnósticos con el fragmento de código y termina con error.
+fn report(filename: &str, input: &str, diagnostics: &[Diagnostic]) -> ! {
+    let map = SourceMap::new(filename, input);
+    for diagnostic in diagnostics {
+        eprintln!("{}", diagnostic.render(&map));
+    }
+    std::process::exit(1);
+}
+
+/// Como `report`, pero solo si hay algún error; las advertencias se
+/// muestran sin cambiar el código de salida.
+fn report_errors(filename: &str, input: &str, diagnostics: &[Diagnostic]) {
+    if diagnostics.iter().any(Diagnostic::is_error) {
+        report(filename, input, diagnostics);
+    }
+    let map = SourceMap::new(filename, input);
+    for diagnostic in diagnostics {
+        eprintln!("{}", diagnostic.render(&map));
+    }
+}
+
+/// Convierte en errores las advertencias por usar declaraciones `old`.
+fn deny_deprecated(diagnostics: &mut [Diagnostic]) {
+    for diagnostic in diagnostics.iter_mut().filter(|diagnostic| diagnostic.code == codes::DEPRECATED_NAME) {
+        diagnostic.severity = Severity::Error;
+    }
+}
+
+/// Muestra la forma de uso y termina con error.
+fn usage() -> ! {
+    eprintln!("{}", tr("cli.usage", &[]));
+    std::process::exit(1);
+}
@@ -0,0 +1,102 @@
+// ============================
+// benches/lexer/main.rs — Rendimiento del lexer en MB/s
+// ============================
+//
+// Uso: `cargo bench -p lemon_core --bench lexer [archivo.yk ...]`
+//
+// Compara el `tokenize` actual con el lexer anterior (un `String` por
+// token), congelado en `previous.rs`, sobre las mismas entradas. Sin
+// argumentos usa un script de interfaz generado (varios miles de líneas,
+// como los que produce el generador de pantallas) y los ejemplos de
+// `tests/parser`. Con argumentos mide esos archivos.
+
+mod previous;
+
+use std::time::{Duration, Instant};
+
+use lemon_core::lexer::tokenize;
+
+/// Tiempo mínimo de medición por entrada.
+const MEASURE_FOR: Duration = Duration::from_secs(2);
+
+fn main() {
+    let paths: Vec<String> = std::env::args().skip(1).filter(|arg| !arg.starts_with("--")).collect();
+    let inputs: Vec<(String, String)> = if paths.is_empty() {
+        let mut inputs = vec![("ui_generado (5000 pantallas)".to_string(), generated_ui_script(5000))];
+        inputs.push(("tests/parser/*.yk".to_string(), fixtures()));
+        inputs
+    } else {
+        paths
+            .into_iter()
+            .map(|path| {
+                let code = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path, e));
+                (path, code)
+            })
+            .collect()
+    };
+
+    for (name, code) in inputs {
+        let (tokens, _) = tokenize(&code);
+        println!("{} ({:.1} KB, {} tokens)", name, code.len() as f64 / 1_000.0, tokens.len());
+        let before = measure("anterior", &code, |code| std::hint::black_box(previous::tokenize(code)).0.len());
+        let after = measure("actual", &code, |code| std::hint::black_box(tokenize(code)).0.len());
+        println!("  {:<10} {:>9.1}x\n", "mejora", after / before);
+    }
+}
+
+/// Ejecuta `lex` sobre `code` durante `MEASURE_FOR`, muestra el resultado y
+/// devuelve los MB/s.
+fn measure(lexer: &str, code: &str, lex: impl Fn(&str) -> usize) -> f64 {
+    let start = Instant::now();
+    let mut runs = 0u32;
+    while start.elapsed() < MEASURE_FOR {
+        lex(std::hint::black_box(code));
+        runs += 1;
+    }
+    let seconds = start.elapsed().as_secs_f64() / f64::from(runs);
+    let throughput = code.len() as f64 / 1_000_000.0 / seconds;
+    println!("  {:<10} {:>9.2} ms/iter {:>9.1} MB/s", lexer, seconds * 1000.0, throughput);
+    throughput
+}
+
+/// Script de interfaz con `screens` pantallas: elementos, atributos,
+/// cadenas con interpolación, comentarios y algo de lógica.
+fn generated_ui_script(screens: usize) -> String {
+    let mut code = String::from("// Generado automáticamente\nui\n\n");
+    for i in 0..screens {
+        code.push_str(&format!(
+            r#"fn pantalla_{i}(usuario, contador)
+    let titulo = "Pantalla {i}: {{usuario.nombre}}"
+    /* Encabezado */
+    window title=titulo ancho=800 alto=600
+        txt text="Hola, {{usuario.nombre}}" id="saludo_{i}"
+        box id="entrada_{i}" placeholder="Escribe aquí..."
+        btn text="Enviar" when=enviar_{i}
+        img src="logo_{i}.png" alt="Logo"
+    end
+    if contador >= 10 and usuario.activo != false
+        contador += 1
+        show "contador: {{contador * 2 ** 3}}"
+    elif contador < 0
+        contador = 0
+    end
+    back contador % 7 == 0 ? usuario : none
+end
+
+"#
+        ));
+    }
+    code
+}
+
+/// Todos los ejemplos de `tests/parser`, concatenados.
+fn fixtures() -> String {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../tests/parser");
+    let mut paths: Vec<_> = std::fs::read_dir(dir)
+        .expect("tests/parser")
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "yk"))
+        .collect();
+    paths.sort();
+    paths.iter().map(|path| std::fs::read_to_string(path).unwrap()).collect::<Vec<_>>().join("\n")
+}
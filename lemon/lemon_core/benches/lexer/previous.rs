@@ -0,0 +1,916 @@
+// ============================
+// benches/lexer/previous.rs — Lexer anterior, congelado para comparar
+// ============================
+//
+// Copia del lexer de antes de pasar a bytes y texto compartido: cada token
+// lleva su propio `String` y el cursor clona el iterador de caracteres para
+// mirar adelante. Solo queda lo que usa `tokenize` (sin modo sin pérdida);
+// `Type` y `Logical` se buscan como entonces, pasando la palabra a
+// minúsculas. No se mantiene: es la referencia fija de `main.rs`.
+
+use lemon_core::diagnostic::{codes, Diagnostic};
+use lemon_core::grammar::{Logical, Symbol, Type, COMPARATORS, LOGICAL_SYMBOLS, OPERATORS, SYMBOLS};
+use lemon_core::i18n::{tr, Locale};
+use lemon_core::lexer::{float_value, integer_value, is_identifier, keyword_pragma};
+use lemon_core::span::Span;
+use lemon_core::token::TokenType;
+use std::sync::LazyLock;
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::GraphemeCursor;
+use unicode_xid::UnicodeXID;
+
+/// Token con su texto propio, como eran antes. La medición solo los crea.
+#[allow(dead_code)]
+pub struct Token {
+    pub token_type: TokenType,
+    pub value: String,
+    pub line: usize,
+    pub column: usize,
+    pub span: Span,
+}
+
+/// Posición dentro del código fuente: byte, línea y columna.
+///
+/// La columna cuenta grupos de grafemas (lo que el lector ve como un
+/// carácter: `ñ` cuenta uno aunque se escriba `n` + tilde combinante), igual
+/// que `SourceMap::location`; el byte exacto queda en `offset` y en los `Span`.
+#[derive(Debug, Clone, Copy)]
+struct Position {
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+/// Recorre el código carácter por carácter llevando la cuenta de la posición.
+/// Todo avance pasa por `bump`, así los saltos de línea dentro de cadenas y
+/// comentarios también actualizan la línea.
+struct Cursor<'a> {
+    code: &'a str,
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    pos: Position,
+    end: usize,       // Byte donde termina el rango que recorre
+    prev_ascii: bool, // El último carácter consumido era ASCII
+}
+
+impl<'a> Cursor<'a> {
+    fn new(code: &'a str) -> Self {
+        Self::at(code, Position { offset: 0, line: 1, column: 1 }, code.len())
+    }
+
+    /// Cursor que recorre solo `code[pos.offset..end]`, con posiciones
+    /// absolutas. Se usa para las expresiones dentro de una interpolación.
+    fn at(code: &'a str, pos: Position, end: usize) -> Self {
+        Cursor {
+            code,
+            chars: code[pos.offset..end].chars().peekable(),
+            pos,
+            end,
+            prev_ascii: true,
+        }
+    }
+
+    /// Texto que falta recorrer.
+    fn rest(&self) -> &'a str {
+        &self.code[self.pos.offset..self.end]
+    }
+
+    /// Carácter actual, sin consumirlo.
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    /// Carácter siguiente al actual, sin consumir nada.
+    fn peek_second(&self) -> Option<char> {
+        self.chars.clone().nth(1)
+    }
+
+    /// Carácter `n` posiciones más adelante (`0` es el actual), sin consumir nada.
+    fn peek_nth(&self, n: usize) -> Option<char> {
+        self.chars.clone().nth(n)
+    }
+
+    /// Consume un carácter y actualiza la posición. La columna solo avanza
+    /// si el carácter empieza un grafema nuevo (una marca combinante no).
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        let starts_grapheme = (self.prev_ascii && c.is_ascii()) || self.is_grapheme_boundary(self.pos.offset);
+        self.pos.offset += c.len_utf8();
+        self.prev_ascii = c.is_ascii();
+        if c == '\n' {
+            self.pos.line += 1;
+            self.pos.column = 1;
+        } else if starts_grapheme {
+            self.pos.column += 1;
+        }
+        Some(c)
+    }
+
+    /// Indica si en el byte `offset` empieza un grupo de grafemas.
+    fn is_grapheme_boundary(&self, offset: usize) -> bool {
+        GraphemeCursor::new(offset, self.code.len(), true)
+            .is_boundary(self.code, 0)
+            .unwrap_or(true)
+    }
+}
+
+/// Construye un token que empieza en `start` y termina en el byte `end`.
+fn make_token(token_type: TokenType, value: String, start: Position, end: usize) -> Token {
+    Token {
+        token_type,
+        value,
+        line: start.line,
+        column: start.column,
+        span: Span::new(start.offset, end),
+    }
+}
+
+/// Error léxico en `start`, cubriendo hasta el byte `end`.
+/// El mensaje sale del catálogo, con el código como clave.
+fn lex_error(code: &'static str, start: Position, end: usize) -> Diagnostic {
+    Diagnostic::error(code, &tr(code, &[])).with_span(Span::new(start.offset, end))
+}
+
+/// Función principal: convierte el código fuente en una lista de tokens.
+///
+/// El análisis nunca se detiene en un error: cada problema se agrega a la
+/// lista de diagnósticos y, si hace falta, se emite un token `Unknown` en su
+/// lugar. Así una sola ejecución informa todos los errores léxicos.
+///
+/// Las palabras clave se reconocen en inglés, o también en español si el
+/// archivo empieza con el pragma `// lang: es` (ver `keyword_pragma`).
+pub fn tokenize(code: &str) -> (Vec<Token>, Vec<Diagnostic>) {
+    let keywords = keyword_pragma(code).map_or(Locale::English, |(locale, _)| locale);
+    let mut tokens = Vec::new();            // Vector donde se guardan los tokens generados
+    let mut diagnostics = Vec::new();       // Errores léxicos encontrados
+    let mut cursor = Cursor::new(code);     // Permite mirar el siguiente carácter sin consumirlo
+
+    lex_range(&mut cursor, keywords, &mut tokens, &mut diagnostics);
+
+    // Agregar token de fin de archivo
+    tokens.push(make_token(TokenType::EOF, "<EOF>".to_string(), cursor.pos, cursor.pos.offset));
+
+    (tokens, diagnostics)
+}
+
+/// Convierte en tokens todo lo que recorre `cursor` (el archivo completo o
+/// el interior de una interpolación), sin agregar el token `EOF`.
+fn lex_range(cursor: &mut Cursor, keywords: Locale, tokens: &mut Vec<Token>, diagnostics: &mut Vec<Diagnostic>) {
+    let mut current = String::new();        // Acumula caracteres para identificadores y palabras clave
+    let mut start = cursor.pos;             // Posición donde empezó la palabra acumulada
+    let mut nesting = 0usize;               // Paréntesis y corchetes abiertos
+
+    while let Some(ch) = cursor.peek() {
+        // =========================
+        // Comentario de una línea
+        // =========================
+        if ch == '/' && cursor.peek_second() == Some('/') {
+            flush_current(&mut current, tokens, diagnostics, start, keywords);
+            // El salto de línea no es parte del comentario: puede terminar una instrucción
+            while let Some(c) = cursor.peek() {
+                if c == '\n' || (c == '\r' && cursor.peek_second() == Some('\n')) {
+                    break;
+                }
+                cursor.bump();
+            }
+            continue;
+        }
+
+        // =========================
+        // Comentario multilínea
+        // =========================
+        if ch == '/' && cursor.peek_second() == Some('*') {
+            flush_current(&mut current, tokens, diagnostics, start, keywords);
+            let comment_start = cursor.pos;
+            cursor.bump(); // consume '/'
+            cursor.bump(); // consume '*'
+            let mut closed = false;
+            while let Some(c) = cursor.bump() {
+                if c == '*' && cursor.peek() == Some('/') {
+                    cursor.bump(); // consume '/'
+                    closed = true;
+                    break;
+                }
+            }
+            if !closed {
+                diagnostics.push(lex_error(codes::UNCLOSED_COMMENT, comment_start, cursor.pos.offset));
+            }
+            continue;
+        }
+
+        // =========================
+        // Literales de cadena: normales, crudas y bloques de texto
+        // =========================
+        if ch == '"' {
+            // `r` pegada a las comillas marca una cadena cruda: `r"\d+"`
+            let raw = current == "r";
+            if raw {
+                current.clear();
+            } else {
+                flush_current(&mut current, tokens, diagnostics, start, keywords);
+            }
+            let string_start = if raw { start } else { cursor.pos };
+            lex_string(cursor, string_start, raw, keywords, tokens, diagnostics);
+            continue;
+        }
+
+        // =========================
+        // Números: 42, 0xFF, 0b1010, 0o17, 1_000, 6.02e23
+        // =========================
+        // Un dígito dentro de un identificador (`x1`) sigue siendo parte del identificador
+        if ch.is_ascii_digit() && current.is_empty() {
+            let (token, error) = lex_number(cursor);
+            diagnostics.extend(error);
+            tokens.push(token);
+            continue;
+        }
+
+        // =========================
+        // Puntuación: operadores, comparadores, lógicos y símbolos
+        // =========================
+        // Gana la coincidencia más larga, así `..=` no se parte en `..` y `=`
+        let op_start = cursor.pos;
+        if let Some(&(text, token_type)) = match_punctuation(cursor.rest()) {
+            flush_current(&mut current, tokens, diagnostics, start, keywords);
+            for _ in text.chars() {
+                cursor.bump();
+            }
+            match token_type {
+                TokenType::Symbol(Symbol::OpenParen | Symbol::OpenBraket) => nesting += 1,
+                TokenType::Symbol(Symbol::CloseParen | Symbol::CloseBraket) => nesting = nesting.saturating_sub(1),
+                _ => {}
+            }
+            tokens.push(make_token(token_type, text.to_string(), op_start, cursor.pos.offset));
+            continue;
+        }
+
+        // =========================
+        // Fin de línea, espacios y tabulaciones
+        // =========================
+        if ch == '\n' || (ch == '\r' && cursor.peek_second() == Some('\n')) {
+            flush_current(&mut current, tokens, diagnostics, start, keywords);
+            while cursor.bump() != Some('\n') {}
+            if nesting == 0 && ends_statement(tokens.last()) {
+                tokens.push(make_token(TokenType::Newline, "\n".to_string(), op_start, cursor.pos.offset));
+            }
+        }
+        else if ch.is_whitespace() {
+            flush_current(&mut current, tokens, diagnostics, start, keywords);
+            cursor.bump();
+        }
+
+        // =========================
+        // Identificadores o palabras clave (UAX #31)
+        // =========================
+        else if is_identifier_continue(ch) {
+            if current.is_empty() {
+                start = cursor.pos;
+            }
+            current.push(ch);
+            cursor.bump();
+        }
+
+        // =========================
+        // Otros caracteres ASCII visibles: @ $ ` ~ ...
+        // =========================
+        else if ch.is_ascii_graphic() {
+            // Se clasifica solo (como token desconocido), así `a@1` no queda pegado en una sola palabra
+            flush_current(&mut current, tokens, diagnostics, start, keywords);
+            current.push(ch);
+            cursor.bump();
+            flush_current(&mut current, tokens, diagnostics, op_start, keywords);
+        }
+
+        // =========================
+        // Caracteres inválidos
+        // =========================
+        else {
+            flush_current(&mut current, tokens, diagnostics, start, keywords);
+            let code = if ch.is_control() { codes::ILLEGAL_CONTROL_CHARACTER } else { codes::ILLEGAL_CHARACTER };
+            cursor.bump();
+            diagnostics.push(lex_error(code, op_start, cursor.pos.offset));
+            tokens.push(make_token(TokenType::Unknown, ch.to_string(), op_start, cursor.pos.offset));
+        }
+    }
+
+    // Procesar la última palabra acumulada (si hay)
+    flush_current(&mut current, tokens, diagnostics, start, keywords);
+}
+
+/// Indica si un salto de línea después de `last` termina la instrucción.
+///
+/// La línea continúa si termina en un operador binario, una asignación (`=`,
+/// `+=`, ...), `,`, `.`, `?.`, `..`, `?`, `??`, `:` o una flecha, porque la
+/// expresión todavía está incompleta:
+///
+/// ```text
+/// let total = precio +
+///     impuesto
+/// ```
+///
+/// Tampoco se emite un salto al inicio del archivo, después de otro salto ni
+/// después de `;`. Dentro de `(` y `[` los saltos nunca cuentan (lo decide
+/// `lex_range`).
+fn ends_statement(last: Option<&Token>) -> bool {
+    let Some(last) = last else { return false };
+    !matches!(
+        last.token_type,
+        TokenType::Newline
+            | TokenType::Operator(_)
+            | TokenType::Comparator(_)
+            | TokenType::Logical(_)
+            | TokenType::Symbol(
+                Symbol::Define
+                    | Symbol::AddAssign
+                    | Symbol::SubtractAssign
+                    | Symbol::MultiplyAssign
+                    | Symbol::DivideAssign
+                    | Symbol::Comma
+                    | Symbol::Dot
+                    | Symbol::SafeDot
+                    | Symbol::Range
+                    | Symbol::RangeInclusive
+                    | Symbol::Question
+                    | Symbol::Coalesce
+                    | Symbol::Colon
+                    | Symbol::Arrow
+                    | Symbol::FatArrow
+                    | Symbol::Semicolon
+            )
+    )
+}
+
+/// Lee una cadena cuyas comillas empiezan en la posición actual; `start` es
+/// el inicio del token (la `r` en las cadenas crudas).
+///
+/// Formas aceptadas:
+/// - `"texto"`: con escapes e interpolaciones.
+/// - `r"C:\ruta"`: cruda, sin escapes ni interpolaciones.
+/// - `"""` ... `"""`: bloque de varias líneas (ver [`text_block_layout`]);
+///   también existe en forma cruda con `r"""`.
+///
+/// Sin interpolaciones se emite un único `StringLiteral` con el texto ya
+/// procesado (escapes aplicados, `{{`/`}}` convertidos en `{`/`}`). Con
+/// interpolaciones (`"Hola, {nombre}"`) se emite:
+///
+/// `InterpolationStart`, y luego fragmentos `StringFragment` alternados con
+/// `{` + los tokens de la expresión + `}`, y por último `InterpolationEnd`.
+fn lex_string(cursor: &mut Cursor, start: Position, raw: bool, keywords: Locale, tokens: &mut Vec<Token>, diagnostics: &mut Vec<Diagnostic>) {
+    let triple = cursor.peek_second() == Some('"') && cursor.peek_nth(2) == Some('"');
+    let delimiter = if triple { 3 } else { 1 };
+    for _ in 0..delimiter {
+        cursor.bump(); // consume las comillas de apertura
+    }
+    let mut fragment_start = cursor.pos;    // Los fragmentos cubren todo el texto, sangría incluida
+
+    // En los bloques el cierre y la sangría se conocen de antemano
+    let block = if triple {
+        match text_block_layout(cursor.code, cursor.pos.offset, raw) {
+            Some(layout) => Some(layout),
+            None => {
+                while cursor.bump().is_some() {}
+                let error = Diagnostic::error(codes::UNCLOSED_STRING, &tr("E0102.block", &[]))
+                    .with_span(Span::new(start.offset, cursor.pos.offset));
+                diagnostics.push(error);
+                let text = cursor.code[start.offset..cursor.pos.offset].to_string();
+                tokens.push(make_token(TokenType::Unknown, text, start, cursor.pos.offset));
+                return;
+            }
+        }
+    } else {
+        None
+    };
+    if let Some(layout) = &block {
+        while cursor.pos.offset < layout.content_start {
+            cursor.bump();
+        }
+        if layout.strip_first_line {
+            skip_indent(cursor, layout.indent, layout.content_end);
+        }
+    }
+
+    let mut parts = Vec::new();             // Fragmentos y tokens de las interpolaciones
+    let mut interpolated = false;
+    let mut fragment = String::new();       // Texto del fragmento actual
+    let mut closing_quote = None;
+
+    loop {
+        let char_start = cursor.pos;
+        if let Some(layout) = &block
+            && char_start.offset >= layout.content_end
+        {
+            // El salto de línea anterior al cierre no es parte del texto
+            while cursor.pos.offset < layout.close {
+                cursor.bump();
+            }
+            closing_quote = Some(cursor.pos);
+            break;
+        }
+        let Some(c) = cursor.bump() else { break };
+        match c {
+            '\\' if !raw => lex_escape(cursor, char_start, &mut fragment, diagnostics),
+            '"' if block.is_none() => {
+                closing_quote = Some(char_start);
+                break;
+            }
+            '\n' if block.is_some() => {
+                fragment.push('\n');
+                if let Some(layout) = &block {
+                    skip_indent(cursor, layout.indent, layout.content_end);
+                }
+            }
+
+            // `{{` y `}}` son llaves literales
+            '{' | '}' if !raw && cursor.peek() == Some(c) => {
+                cursor.bump();
+                fragment.push(c);
+            }
+
+            '{' if !raw => match find_interpolation_end(cursor.code, cursor.pos.offset) {
+                Some(close) => {
+                    interpolated = true;
+                    push_fragment(&mut parts, &mut fragment, fragment_start, char_start.offset);
+                    parts.push(make_token(TokenType::Symbol(Symbol::OpenBrace), "{".to_string(), char_start, cursor.pos.offset));
+
+                    // La expresión se analiza como código normal, con posiciones absolutas
+                    let mut inner = Cursor::at(cursor.code, cursor.pos, close);
+                    lex_range(&mut inner, keywords, &mut parts, diagnostics);
+                    while cursor.pos.offset < close {
+                        cursor.bump();
+                    }
+
+                    let close_start = cursor.pos;
+                    cursor.bump(); // consume '}'
+                    parts.push(make_token(TokenType::Symbol(Symbol::CloseBrace), "}".to_string(), close_start, cursor.pos.offset));
+                    fragment_start = cursor.pos;
+                }
+                None => {
+                    // Sin `}` de cierre: se informa y la llave queda como texto
+                    diagnostics.push(interpolation_error("E0109.unclosed", char_start, cursor.pos.offset));
+                    fragment.push('{');
+                }
+            },
+            '}' if !raw => {
+                diagnostics.push(interpolation_error("E0109.stray_close", char_start, cursor.pos.offset));
+                fragment.push('}');
+            }
+
+            _ => fragment.push(c),
+        }
+    }
+
+    let Some(closing_quote) = closing_quote else {
+        // La cadena se comió el resto del archivo: queda como un único token desconocido
+        diagnostics.push(lex_error(codes::UNCLOSED_STRING, start, cursor.pos.offset));
+        let text = cursor.code[start.offset..cursor.pos.offset].to_string();
+        tokens.push(make_token(TokenType::Unknown, text, start, cursor.pos.offset));
+        return;
+    };
+    if block.is_some() {
+        for _ in 0..delimiter {
+            cursor.bump(); // consume las comillas de cierre
+        }
+    }
+
+    if !interpolated {
+        tokens.push(make_token(TokenType::StringLiteral, fragment, start, cursor.pos.offset));
+        return;
+    }
+
+    push_fragment(&mut parts, &mut fragment, fragment_start, closing_quote.offset);
+    let opening = &cursor.code[start.offset..start.offset + delimiter];
+    tokens.push(make_token(TokenType::InterpolationStart, opening.to_string(), start, start.offset + delimiter));
+    tokens.extend(parts);
+    let closing = &cursor.code[closing_quote.offset..cursor.pos.offset];
+    tokens.push(make_token(TokenType::InterpolationEnd, closing.to_string(), closing_quote, cursor.pos.offset));
+}
+
+/// Procesa una secuencia de escape; la `\` en `start` ya fue consumida.
+///
+/// Escapes válidos: `\n`, `\t`, `\r`, `\0`, `\\`, `\"`, `\xNN` (ASCII,
+/// hasta `\x7F`) y `\u{...}` (de 1 a 6 dígitos hexadecimales). Un escape
+/// inválido se informa y su texto se conserva tal cual, sin invalidar la cadena.
+fn lex_escape(cursor: &mut Cursor, start: Position, out: &mut String, diagnostics: &mut Vec<Diagnostic>) {
+    let Some(escaped) = cursor.bump() else { return };
+    let value = match escaped {
+        'n' => Ok('\n'),
+        't' => Ok('\t'),
+        'r' => Ok('\r'),
+        '0' => Ok('\0'),
+        '\\' => Ok('\\'),
+        '"' => Ok('"'),
+        'x' => {
+            let mut digits = String::new();
+            while digits.len() < 2 && cursor.peek().is_some_and(|c| c.is_ascii_hexdigit()) {
+                digits.extend(cursor.bump());
+            }
+            match u8::from_str_radix(&digits, 16) {
+                Ok(byte) if digits.len() == 2 && byte.is_ascii() => Ok(byte as char),
+                _ => Err(tr("E0103.hex", &[])),
+            }
+        }
+        'u' => {
+            let mut digits = String::new();
+            let braced = cursor.peek() == Some('{');
+            if braced {
+                cursor.bump();
+                while cursor.peek().is_some_and(|c| c.is_ascii_hexdigit()) {
+                    digits.extend(cursor.bump());
+                }
+            }
+            let closed = braced && cursor.peek() == Some('}');
+            if closed {
+                cursor.bump();
+            }
+            if !closed || digits.is_empty() || digits.len() > 6 {
+                Err(tr("E0103.unicode", &[]))
+            } else {
+                // Los sustitutos (`D800`-`DFFF`) y los valores mayores a `10FFFF` no son caracteres
+                u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| tr("E0103.scalar", &[&digits]))
+            }
+        }
+        _ => Err(tr(codes::INVALID_ESCAPE, &[])),
+    };
+
+    match value {
+        Ok(c) => out.push(c),
+        Err(message) => {
+            let error = Diagnostic::error(codes::INVALID_ESCAPE, &message)
+                .with_span(Span::new(start.offset, cursor.pos.offset))
+                .with_help(&tr("E0103.help", &[]));
+            diagnostics.push(error);
+            out.push_str(&cursor.code[start.offset..cursor.pos.offset]);
+        }
+    }
+}
+
+/// Forma de un bloque `"""`, calculada antes de leer su contenido.
+struct TextBlockLayout {
+    /// Byte donde empieza el texto (después de la primera línea si está vacía).
+    content_start: usize,
+    /// Byte donde termina el texto (antes del último salto si la línea del cierre está vacía).
+    content_end: usize,
+    /// Byte de las comillas de cierre.
+    close: usize,
+    /// Espacios que se quitan al inicio de cada línea.
+    indent: usize,
+    /// Si la primera línea del texto también lleva sangría que quitar.
+    strip_first_line: bool,
+}
+
+/// Ubica el cierre de un bloque `"""` cuyo contenido empieza en el byte
+/// `from` y calcula la sangría común.
+///
+/// Si la línea de apertura y la del cierre solo tienen espacios, no forman
+/// parte del texto. La sangría que se quita es la menor entre las líneas no
+/// vacías y la línea del cierre, así que el texto se puede alinear con el
+/// código que lo rodea:
+///
+/// ```text
+///     let poema = """
+///         Caminante, no hay camino,
+///           se hace camino al andar.
+///         """
+/// ```
+///
+/// produce `"Caminante, no hay camino,\n  se hace camino al andar."`.
+fn text_block_layout(code: &str, from: usize, raw: bool) -> Option<TextBlockLayout> {
+    let bytes = code.as_bytes();
+    let mut close = from;
+    loop {
+        match bytes.get(close)? {
+            b'\\' if !raw => close += 2,
+            b'"' if code[close..].starts_with("\"\"\"") => break,
+            _ => close += 1,
+        }
+    }
+
+    let content = &code[from..close];
+    let is_blank = |line: &str| line.trim_matches([' ', '\t', '\r']).is_empty();
+    let indent_of = |line: &str| line.len() - line.trim_start_matches([' ', '\t']).len();
+
+    let mut content_start = from;
+    let mut content_end = close;
+    let mut indent = usize::MAX;
+    let mut lines: Vec<&str> = content.split('\n').collect();
+
+    // Primera línea vacía: el texto empieza en la siguiente
+    let strip_first_line = lines.len() > 1 && is_blank(lines[0]);
+    if strip_first_line {
+        content_start += lines[0].len() + 1;
+        lines.remove(0);
+    } else if lines.len() > 1 {
+        lines.remove(0);
+    } else {
+        // Bloque de una sola línea: no hay sangría que quitar
+        return Some(TextBlockLayout { content_start, content_end, close, indent: 0, strip_first_line: false });
+    }
+
+    // Línea del cierre vacía: su sangría cuenta y su salto de línea no es texto
+    if let Some(last) = lines.last()
+        && is_blank(last)
+    {
+        indent = indent_of(last);
+        content_end = close - last.len() - 1;
+        lines.pop();
+    }
+    for line in lines.iter().filter(|line| !is_blank(line)) {
+        indent = indent.min(indent_of(line));
+    }
+
+    let indent = if indent == usize::MAX { 0 } else { indent };
+    Some(TextBlockLayout { content_start: content_start.min(content_end), content_end, close, indent, strip_first_line })
+}
+
+/// Consume hasta `indent` espacios o tabulaciones al inicio de una línea de un bloque.
+fn skip_indent(cursor: &mut Cursor, indent: usize, end: usize) {
+    for _ in 0..indent {
+        if cursor.pos.offset < end && matches!(cursor.peek(), Some(' ' | '\t')) {
+            cursor.bump();
+        } else {
+            break;
+        }
+    }
+}
+
+/// Agrega el fragmento de texto acumulado como `StringFragment`. Se omite
+/// solo si no cubre nada del código fuente: uno que quedó vacío tras quitar
+/// la sangría se conserva para que el texto original siga completo.
+fn push_fragment(parts: &mut Vec<Token>, fragment: &mut String, start: Position, end: usize) {
+    if end > start.offset {
+        parts.push(make_token(TokenType::StringFragment, std::mem::take(fragment), start, end));
+    }
+}
+
+/// Error de interpolación con el mensaje `key` del catálogo.
+fn interpolation_error(key: &str, start: Position, end: usize) -> Diagnostic {
+    Diagnostic::error(codes::INVALID_INTERPOLATION, &tr(key, &[])).with_span(Span::new(start.offset, end))
+}
+
+/// Busca el `}` que cierra una interpolación cuyo contenido empieza en el
+/// byte `from`. Respeta llaves anidadas y cadenas dentro de la expresión
+/// (`"{f("a")}"`). La expresión debe terminar en la misma línea.
+fn find_interpolation_end(code: &str, from: usize) -> Option<usize> {
+    let bytes = code.as_bytes();
+    let mut depth = 0;
+    let mut i = from;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' => depth += 1,
+            b'}' if depth == 0 => return Some(i),
+            b'}' => depth -= 1,
+            b'"' => {
+                i = skip_string(code, i + 1)?;
+                continue;
+            }
+            b'\n' => return None,
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Salta una cadena anidada cuyo contenido empieza en el byte `from` y
+/// devuelve la posición después de su `"` de cierre. Las interpolaciones
+/// internas se saltan completas, así que `"{"{x}"}"` se reconoce bien.
+/// Como la expresión exterior es de una sola línea, la cadena también.
+fn skip_string(code: &str, from: usize) -> Option<usize> {
+    let bytes = code.as_bytes();
+    let mut i = from;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 1,
+            b'"' => return Some(i + 1),
+            b'{' | b'}' if bytes.get(i + 1) == Some(&bytes[i]) => i += 1,
+            b'{' => i = find_interpolation_end(code, i + 1)?,
+            b'\n' => return None,
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Lee un literal numérico que empieza en la posición actual del cursor.
+///
+/// Acepta enteros decimales, hexadecimales (`0xFF`), binarios (`0b1010`) y
+/// octales (`0o17`), decimales con parte fraccionaria y/o exponente
+/// (`6.02e23`, `1e-3`) y `_` como separador (`1_000_000`). El valor del
+/// token es el texto tal como está escrito; ver `integer_value` y `float_value`.
+///
+/// Si el número sigue con letras o dígitos que no le corresponden (`12abc`,
+/// `0b102`) o no cabe en su tipo, todo el texto queda como un token `Unknown`
+/// junto con su diagnóstico.
+fn lex_number(cursor: &mut Cursor) -> (Token, Option<Diagnostic>) {
+    let start = cursor.pos;
+    let radix = match (cursor.peek(), cursor.peek_second()) {
+        (Some('0'), Some('x' | 'X')) => 16,
+        (Some('0'), Some('b' | 'B')) => 2,
+        (Some('0'), Some('o' | 'O')) => 8,
+        _ => 10,
+    };
+
+    let mut is_float = false;
+    let has_digits = if radix == 10 {
+        eat_digits(cursor, 10);
+        // Solo es decimal si después del punto hay un dígito (`1.5`, no `obj.1`)
+        if cursor.peek() == Some('.') && cursor.peek_second().is_some_and(|c| c.is_ascii_digit()) {
+            cursor.bump(); // consume '.'
+            eat_digits(cursor, 10);
+            is_float = true;
+        }
+        // Exponente: `e5`, `E+5`, `e-5`
+        if matches!(cursor.peek(), Some('e' | 'E')) {
+            let sign = usize::from(matches!(cursor.peek_second(), Some('+' | '-')));
+            if cursor.peek_nth(1 + sign).is_some_and(|c| c.is_ascii_digit()) {
+                for _ in 0..=sign {
+                    cursor.bump(); // consume 'e' y el signo
+                }
+                eat_digits(cursor, 10);
+                is_float = true;
+            }
+        }
+        true
+    } else {
+        cursor.bump(); // consume '0'
+        cursor.bump(); // consume 'x', 'b' u 'o'
+        eat_digits(cursor, radix)
+    };
+
+    // Letras o dígitos pegados al número lo vuelven inválido
+    let mut malformed = !has_digits;
+    while cursor.peek().is_some_and(is_identifier_continue) {
+        malformed = true;
+        cursor.bump();
+    }
+
+    let end = cursor.pos.offset;
+    let text = cursor.code[start.offset..end].to_string();
+    let (token_type, error) = if malformed {
+        let mut error = Diagnostic::error(codes::MALFORMED_NUMBER, &tr(codes::MALFORMED_NUMBER, &[&text]))
+            .with_span(Span::new(start.offset, end));
+        if radix == 10 && !is_float {
+            error = error.with_help(&tr("E0107.identifier", &[]));
+        }
+        (TokenType::Unknown, Some(error))
+    } else if is_float {
+        match float_value(&text) {
+            Some(_) => (TokenType::Float, None),
+            None => (TokenType::Unknown, Some(out_of_range(&text, start, end))),
+        }
+    } else {
+        match integer_value(&text) {
+            Some(_) => (TokenType::Integer, None),
+            None => (TokenType::Unknown, Some(out_of_range(&text, start, end))),
+        }
+    };
+    (make_token(token_type, text, start, end), error)
+}
+
+/// Consume dígitos de la base `radix` y separadores `_`.
+/// Indica si se consumió al menos un dígito.
+fn eat_digits(cursor: &mut Cursor, radix: u32) -> bool {
+    let mut any = false;
+    while let Some(c) = cursor.peek() {
+        if c.is_digit(radix) {
+            any = true;
+        } else if c != '_' {
+            break;
+        }
+        cursor.bump();
+    }
+    any
+}
+
+/// Error de número que no cabe en su tipo (`i64` o `f64` finito).
+fn out_of_range(text: &str, start: Position, end: usize) -> Diagnostic {
+    Diagnostic::error(codes::NUMBER_OUT_OF_RANGE, &tr(codes::NUMBER_OUT_OF_RANGE, &[&text]))
+        .with_span(Span::new(start.offset, end))
+        .with_note(&tr("E0108.note", &[]))
+}
+
+/// Si hay texto acumulado, clasifícalo como un token y agrégalo al vector.
+/// `start` es la posición del primer carácter acumulado. Una palabra que no
+/// se reconoce se emite como `Unknown` junto con su diagnóstico.
+fn flush_current(
+    current: &mut String,
+    tokens: &mut Vec<Token>,
+    diagnostics: &mut Vec<Diagnostic>,
+    start: Position,
+    keywords: Locale,
+) {
+    if current.is_empty() {
+        return;
+    }
+    let mut token = classify(current, start.line, start.column, keywords);
+    token.span = Span::new(start.offset, start.offset + current.len());
+    if token.token_type == TokenType::Unknown {
+        diagnostics.push(lex_error(codes::UNKNOWN_TOKEN, start, token.span.end));
+    }
+    tokens.push(token);
+    current.clear();
+}
+
+/// Clasifica una palabra como keyword, tipo, operador, identificador, número, etc.
+/// `keywords` es el idioma de las palabras clave del archivo.
+/// El `span` lo completa quien llama.
+fn classify(word: &str, line: usize, column: usize, keywords: Locale) -> Token {
+    use TokenType::*;
+    let value = word.to_string();
+    let span = Span::default();
+
+    // Las palabras contextuales (`box`, `window`, ...) se entregan como
+    // identificadores; el parser las reconoce por su posición.
+    if let Some(kw) = lemon_core::grammar::Keyword::parse_in(word, keywords)
+        && !kw.is_contextual()
+    {
+        return Token { token_type: Keyword(kw), value, line, column, span };
+    }
+
+    if let Some(ty) = type_name(word) {
+        return Token { token_type: Type(ty), value, line, column, span };
+    }
+
+    if let Some(op) = lemon_core::grammar::Operator::parse(word) {
+        return Token { token_type: Operator(op), value, line, column, span };
+    }
+
+    if let Some(cmp) = lemon_core::grammar::Comparator::parse(word) {
+        return Token { token_type: Comparator(cmp), value, line, column, span };
+    }
+
+    if let Some(log) = logical(word) {
+        return Token { token_type: Logical(log), value, line, column, span };
+    }
+
+    if let Some(comment) = lemon_core::grammar::Comment::parse(word) {
+        return Token { token_type: Comment(comment), value, line, column, span };
+    }
+
+    // Identificador válido: se guarda en NFC, así `año` escrito con `ñ`
+    // precompuesta o con `n` + tilde combinante es el mismo nombre
+    if is_identifier(word) {
+        let value = word.nfc().collect();
+        return Token { token_type: Identifier, value, line, column, span };
+    }
+
+    // Token desconocido
+    Token { token_type: Unknown, value, line, column, span }
+}
+
+/// `Type::from_str` de entonces.
+fn type_name(s: &str) -> Option<Type> {
+    match s.to_lowercase().as_str() {
+        "int"    => Some(Type::Int),
+        "float"  => Some(Type::Float),
+        "string" => Some(Type::String),
+        "bool"   => Some(Type::Bool),
+        "null"   => Some(Type::Null),
+        "list"   => Some(Type::List),
+        "matrix" => Some(Type::Matrix),
+        "map"    => Some(Type::Map),
+        _        => None,
+    }
+}
+
+/// `Logical::from_str` de entonces.
+fn logical(s: &str) -> Option<Logical> {
+    match s.to_lowercase().as_str() {
+        "and" => Some(Logical::And),
+        "&&"  => Some(Logical::And),
+        "or"  => Some(Logical::Or),
+        "||"  => Some(Logical::Or),
+        "not" => Some(Logical::Not),
+        "!"   => Some(Logical::Not),
+        _     => None,
+    }
+}
+
+/// Carácter que puede seguir dentro de un identificador: `XID_Continue`
+/// (letras, dígitos, marcas combinantes y `_`).
+fn is_identifier_continue(c: char) -> bool {
+    c.is_xid_continue()
+}
+
+/// Tabla de puntuación, armada con las tablas de `grammar` (operadores,
+/// comparadores, lógicos simbólicos y símbolos) y ordenada de mayor a menor
+/// largo: la primera entrada que coincide es la más larga.
+static PUNCTUATION: LazyLock<Vec<(&'static str, TokenType)>> = LazyLock::new(|| {
+    let mut table: Vec<(&'static str, TokenType)> = OPERATORS
+        .iter()
+        .map(|&(text, op)| (text, TokenType::Operator(op)))
+        .chain(COMPARATORS.iter().map(|&(text, cmp)| (text, TokenType::Comparator(cmp))))
+        .chain(LOGICAL_SYMBOLS.iter().map(|&(text, log)| (text, TokenType::Logical(log))))
+        .chain(SYMBOLS.iter().map(|&(text, symbol)| (text, TokenType::Symbol(symbol))))
+        .collect();
+    table.sort_by_key(|(text, _)| std::cmp::Reverse(text.len()));
+    table
+});
+
+/// Puntuación más larga al inicio de `rest`, con su texto.
+fn match_punctuation(rest: &str) -> Option<&'static (&'static str, TokenType)> {
+    PUNCTUATION.iter().find(|(text, _)| rest.starts_with(text))
+}
@@ -1,16 +1,47 @@
+use crate::ast::node::NodeId;
+use crate::span::Span;
+use serde::Serialize;
+
+/// Nodo de expresión del AST: qué expresión es (`kind`) y qué rango del
+/// código fuente ocupa (`span`).
+#[derive(Debug, Clone, Serialize)]
+pub struct Expression {
+    /// Identidad del nodo.
+    #[serde(skip)]
+    pub id: NodeId,
+    /// Tipo de expresión y sus hijos.
+    pub kind: ExpressionKind,
+    /// Rango de bytes que cubre la expresión completa.
+    pub span: Span,
+}
+
+impl Expression {
+    /// Crea un nodo de expresión.
+    pub fn new(kind: ExpressionKind, span: Span) -> Self {
+        Expression { id: NodeId::next(), kind, span }
+    }
+}
+
 /// Representa cualquier expresión que produce un valor en el lenguaje Yuka.
 /// 
 /// Las expresiones incluyen literales, operaciones, llamadas a funciones,
 /// acceso a propiedades, entre otros. Se usan en contextos donde se espera
 /// una evaluación que retorne un valor.
-#[derive(Debug, Clone)]
-pub enum Expression {
-    /// Literal numérico, por ejemplo: `42`, `3.14`, etc.
-    Number(f64),
+#[derive(Debug, Clone, Serialize)]
+pub enum ExpressionKind {
+    /// Literal entero, por ejemplo: `42`, `0xFF`, `1_000`.
+    Integer(i64),
+
+    /// Literal decimal, por ejemplo: `3.14`, `6.02e23`.
+    Float(f64),
 
     /// Literal de texto, como `"hola"` o `"Yuka"`.
     String(String),
 
+    /// Cadena con expresiones incrustadas, como `"Hola, {nombre}"`.
+    /// Las partes se evalúan en orden y se concatenan como texto.
+    Interpolated(Vec<InterpolationPart>),
+
     /// Valor booleano literal: `true` o `false`.
     Boolean(bool),
 
@@ -63,6 +94,20 @@ pub enum Expression {
         value: Box<Expression>,
     },
 
+    /// Asignación compuesta sobre una variable o una propiedad.
+    /// Ejemplo: `x += 1` o `persona.edad -= 1`
+    ///
+    /// A diferencia de `objetivo = objetivo op valor`, el objeto de una
+    /// propiedad se evalúa una sola vez.
+    CompoundAssign {
+        /// Variable (`Variable`) o propiedad (`Get`) que se modifica.
+        target: Box<Expression>,
+        /// Operador que combina el valor actual con el nuevo.
+        op: BinaryOp,
+        /// Valor de la derecha.
+        value: Box<Expression>,
+    },
+
     /// Acceso a una propiedad de un objeto.
     /// Ejemplo: `persona.nombre`
     Get {
@@ -102,10 +147,19 @@ pub enum Expression {
     None,
 }
 
+/// Parte de una cadena interpolada.
+#[derive(Debug, Clone, Serialize)]
+pub enum InterpolationPart {
+    /// Texto literal, ya con escapes y `{{`/`}}` resueltos.
+    Literal(String),
+    /// Expresión entre llaves, por ejemplo: `{edad + 1}`.
+    Expression(Expression),
+}
+
 /// Enum que define todos los operadores binarios que soporta Yuka.
 ///
 /// Estos operadores se usan en expresiones que combinan dos operandos.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum BinaryOp {
     /// Suma: `+`
     Add,
@@ -115,6 +169,10 @@ pub enum BinaryOp {
     Mul,
     /// División: `/`
     Div,
+    /// Módulo o residuo: `%`
+    Mod,
+    /// Potenciación (asociativa a la derecha): `^`
+    Pow,
 
     /// Igualdad: `==`
     Eq,
@@ -135,10 +193,32 @@ pub enum BinaryOp {
     Or,
 }
 
+impl BinaryOp {
+    /// Símbolo del operador como se escribe en el código.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BinaryOp::Add => "+",
+            BinaryOp::Sub => "-",
+            BinaryOp::Mul => "*",
+            BinaryOp::Div => "/",
+            BinaryOp::Mod => "%",
+            BinaryOp::Pow => "^",
+            BinaryOp::Eq => "==",
+            BinaryOp::Neq => "!=",
+            BinaryOp::Gt => ">",
+            BinaryOp::Lt => "<",
+            BinaryOp::Gte => ">=",
+            BinaryOp::Lte => "<=",
+            BinaryOp::And => "&&",
+            BinaryOp::Or => "||",
+        }
+    }
+}
+
 /// Enum que define todos los operadores unarios disponibles.
 ///
 /// Los operadores unarios actúan sobre una sola expresión.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum UnaryOp {
     /// Negación lógica: `!expr`
     Not,
@@ -146,10 +226,11 @@ pub enum UnaryOp {
     Neg,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum Literal {
     Boolean(bool),
-    Number(f64),
+    Integer(i64),
+    Float(f64),
     String(String),
     None,
 }
@@ -1,5 +1,10 @@
 pub mod expressions;
+pub mod node;
 pub mod statements;
+pub mod tree;
+pub mod types;
 
 pub use expressions::*;
-pub use statements::*;
\ No newline at end of file
+pub use node::*;
+pub use statements::*;
+pub use types::*;
@@ -0,0 +1,19 @@
+use serde::Serialize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Identidad de un nodo del AST (expresión, instrucción o parámetro).
+///
+/// Cada nodo recibe una distinta al crearse y la conserva al clonarse, así
+/// que sirve de clave estable para anotar el árbol desde afuera (por
+/// ejemplo, a qué declaración se refiere cada uso en `resolve`), aun cuando
+/// dos nodos compartan el mismo rango.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub struct NodeId(pub usize);
+
+impl NodeId {
+    /// Identidad nueva, distinta de todas las entregadas antes.
+    pub fn next() -> Self {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        NodeId(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
@@ -1,30 +1,94 @@
 use crate::ast::expressions::Expression;
+use crate::ast::node::NodeId;
+use crate::ast::types::TypeExpr;
+use crate::grammar::Keyword;
+use crate::span::Span;
+use serde::Serialize;
+
+/// Nodo de instrucción del AST: qué instrucción es (`kind`) y qué rango del
+/// código fuente ocupa (`span`).
+#[derive(Debug, Clone, Serialize)]
+pub struct Statement {
+    /// Identidad del nodo.
+    #[serde(skip)]
+    pub id: NodeId,
+    /// Tipo de instrucción y sus hijos.
+    pub kind: StatementKind,
+    /// Rango de bytes que cubre la instrucción completa (incluido su bloque).
+    pub span: Span,
+}
+
+impl Statement {
+    /// Crea un nodo de instrucción.
+    pub fn new(kind: StatementKind, span: Span) -> Self {
+        Statement { id: NodeId::next(), kind, span }
+    }
+}
+
+/// Parámetro de una función: `precio` o `precio: float`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Param {
+    /// Identidad del nodo.
+    #[serde(skip)]
+    pub id: NodeId,
+    /// Nombre del parámetro.
+    pub name: String,
+    /// Tipo anotado después de `:` (opcional).
+    pub ty: Option<TypeExpr>,
+    /// Rango de bytes del parámetro, con su tipo.
+    pub span: Span,
+}
+
+/// Atributo `old`: marca una declaración como obsoleta, con un mensaje
+/// opcional que sugiere qué usar en su lugar (`old("usa nueva") fn vieja()`).
+#[derive(Debug, Clone, Serialize)]
+pub struct Deprecation {
+    /// Texto entre paréntesis (opcional).
+    pub message: Option<String>,
+    /// Rango de bytes del atributo, con su mensaje.
+    pub span: Span,
+}
 
 /// Representa una instrucción completa del lenguaje Yuka.
 ///
 /// A diferencia de `Expression`, los `Statement` no siempre producen un valor.
 /// Su propósito es ejecutar acciones, como declarar variables, controlar flujo,
 /// agrupar instrucciones, entre otros.
-#[derive(Debug, Clone)]
-pub enum Statement {
+#[derive(Debug, Clone, Serialize)]
+pub enum StatementKind {
     /// Declaración de variable con valor obligatorio.
     ///
-    /// Ejemplo: `let x = 5;`
+    /// Ejemplo: `let x = 5;`, `let nombre: string = "Ana"` o `let PI sure = 3.1416`
     Let {
         /// Nombre de la variable.
         name: String,
+        /// Tipo anotado después de `:` (opcional).
+        ty: Option<TypeExpr>,
+        /// Declarada con `sure`: no se puede reasignar ni borrar. Solo
+        /// aparece en la forma serializada cuando es `true`.
+        #[serde(skip_serializing_if = "std::ops::Not::not")]
+        sure: bool,
+        /// Marcada como obsoleta con `old` (solo se serializa si existe).
+        #[serde(skip_serializing_if = "Option::is_none")]
+        deprecated: Option<Deprecation>,
         /// Valor que se le asigna al declararla.
         value: Expression,
     },
 
     /// Declaración de función, con o sin nombre.
     ///
-    /// Ejemplo: `fun greet(name) { ... }`
+    /// Ejemplo: `fun greet(name) { ... }`, `fn doble(x: int) -> int ... end` u
+    /// `old("usa nueva") fn vieja() ... end`
     Function {
         /// Nombre de la función (opcional si es anónima).
         name: Option<String>,
-        /// Lista de nombres de parámetros.
-        params: Vec<String>,
+        /// Parámetros, con su tipo si está anotado.
+        params: Vec<Param>,
+        /// Tipo del valor devuelto, anotado después de `->` (opcional).
+        return_type: Option<TypeExpr>,
+        /// Marcada como obsoleta con `old` (solo se serializa si existe).
+        #[serde(skip_serializing_if = "Option::is_none")]
+        deprecated: Option<Deprecation>,
         /// Bloque de instrucciones que conforman el cuerpo de la función.
         body: Vec<Statement>,
     },
@@ -64,7 +128,7 @@ pub enum Statement {
     /// Bucle estilo C: `for (init; condition; increment) { ... }`
     ///
     /// Ejemplo:
-    /// ```
+    /// ```text
     /// for (let i = 0; i < 10; i = i + 1) { ... }
     /// ```
     ForCStyle {
@@ -103,7 +167,7 @@ pub enum Statement {
     /// Bloque de instrucciones agrupadas entre llaves `{ ... }`.
     ///
     /// Ejemplo:
-    /// ```
+    /// ```text
     /// {
     ///     let x = 1;
     ///     print(x);
@@ -111,6 +175,18 @@ pub enum Statement {
     /// ```
     Block(Vec<Statement>),
 
+    /// Manejo de errores: ejecuta `body` y, si falla, ejecuta `handler`.
+    ///
+    /// Ejemplo: `try ... fail e ... end`
+    Try {
+        /// Instrucciones protegidas.
+        body: Box<Statement>,
+        /// Nombre opcional de la variable que recibe el mensaje de error.
+        error: Option<String>,
+        /// Rama ejecutada cuando `body` produce un error (opcional).
+        handler: Option<Box<Statement>>,
+    },
+
     /// Finaliza la ejecución de un bucle o estructura repetitiva.
     ///
     /// Equivalente a `break;`
@@ -133,16 +209,48 @@ pub enum Statement {
 
     /// Declaración de variable con o sin inicialización.
     ///
-    /// Ejemplo: `let x;` o `let x = 42;`
+    /// Ejemplo: `let x;` o `let x: int`
     Variable {
         /// Nombre de la variable declarada.
         name: String,
+        /// Tipo anotado después de `:` (opcional).
+        ty: Option<TypeExpr>,
         /// Valor opcional con el que se inicializa.
         initializer: Option<Expression>,
+        /// Marcada como obsoleta con `old` (solo se serializa si existe).
+        #[serde(skip_serializing_if = "Option::is_none")]
+        deprecated: Option<Deprecation>,
     },
 
-    /// Llamada a una función interna del lenguaje o del runtime.
+    /// Elimina una variable del ámbito donde se declaró.
+    ///
+    /// Ejemplo: `del edad`
+    Delete(String),
+
+    /// Llamada a una función interna del lenguaje o del runtime, con sus
+    /// argumentos.
     ///
-    /// Ejemplo: `@print("Hola")`
-    BuiltinCall(String, Expression),
+    /// Ejemplo: `print("Hola", nombre)`
+    BuiltinCall(String, Vec<Expression>),
+
+    /// Activa la interfaz gráfica. Desde aquí hasta el final del bloque, los
+    /// elementos (`btn`, `txt`, `box`, ...) son palabras clave en cualquier
+    /// instrucción.
+    ///
+    /// Ejemplo: `ui`
+    Ui,
+
+    /// Elemento o evento de la interfaz gráfica.
+    ///
+    /// Ejemplo: `btn text="Click me" when=mi_evento` o `wrap ... end`
+    UiElement {
+        /// Palabra clave del elemento (`btn`, `wrap`, `wait`, ...).
+        element: Keyword,
+        /// Valor sin nombre, como el `1000` de `wait 1000` (opcional).
+        argument: Option<Expression>,
+        /// Atributos `nombre=valor`, en el orden en que aparecen.
+        attributes: Vec<(String, Expression)>,
+        /// Contenido de los contenedores y eventos (`wrap`, `div`, `window`, `tick`, `click`).
+        body: Option<Box<Statement>>,
+    },
 }
@@ -0,0 +1,158 @@
+// ============================
+// tree.rs — AST como árbol de texto
+// ============================
+
+use crate::ast::statements::Statement;
+use serde_json::Value;
+
+/// Dibuja el AST como un árbol, un nodo por línea con su rango de bytes.
+///
+/// El árbol sale de la forma serializada del AST (la misma que `yuka ast
+/// --format json`), así que un nodo nuevo aparece sin tocar esta función.
+/// Los valores simples (nombres, números, operadores) van en la línea del
+/// nodo y los hijos debajo, con el nombre de su campo.
+///
+/// ```
+/// # use lemon_core::ast::tree::render;
+/// let statements = lemon_core::parse("let total = 1 + 2\n").unwrap();
+/// assert_eq!(
+///     render(&statements),
+///     "Let name=\"total\" [0..17]\n\
+///      └─ value: Binary op=\"Add\" [12..17]\n   \
+///         ├─ left: Integer 1 [12..13]\n   \
+///         └─ right: Integer 2 [16..17]\n"
+/// );
+/// ```
+pub fn render(statements: &[Statement]) -> String {
+    let mut out = String::new();
+    for statement in statements {
+        let value = serde_json::to_value(statement).unwrap_or(Value::Null);
+        write_node(&mut out, "", "", "", &value);
+    }
+    out
+}
+
+/// Escribe `value` en una línea (precedida por `connector` y `field`) y
+/// sus hijos debajo, con `prefix` como sangría.
+fn write_node(out: &mut String, prefix: &str, connector: &str, field: &str, value: &Value) {
+    let (label, children) = describe(value);
+    out.push_str(prefix);
+    out.push_str(connector);
+    if !field.is_empty() {
+        out.push_str(field);
+        out.push(':');
+        if !label.is_empty() {
+            out.push(' ');
+        }
+    }
+    out.push_str(&label);
+    out.push('\n');
+
+    // Los hijos quedan debajo del conector de este nodo
+    let prefix = match connector {
+        "├─ " => format!("{prefix}│  "),
+        "└─ " => format!("{prefix}   "),
+        _ => prefix.to_string(),
+    };
+    for (i, (name, child)) in children.iter().enumerate() {
+        let connector = if i + 1 == children.len() { "└─ " } else { "├─ " };
+        write_node(out, &prefix, connector, name, child);
+    }
+}
+
+/// Texto de la línea de un nodo e hijos que van debajo (con su campo).
+fn describe(value: &Value) -> (String, Vec<(String, &Value)>) {
+    match value {
+        // Nodo del AST: `kind` dice qué es y `span` dónde está
+        Value::Object(map) if is_node(value) => {
+            let (label, children) = describe(&map["kind"]);
+            let span = &map["span"];
+            let range = format!("[{}..{}]", span["start"], span["end"]);
+            let label = if label.is_empty() { range } else { format!("{label} {range}") };
+            (label, children)
+        }
+        // Variante de un enum con datos: `{"Binary": {...}}`
+        Value::Object(map) if map.len() == 1 => {
+            let (variant, payload) = map.iter().next().expect("un solo campo");
+            let mut label = variant.clone();
+            let mut children = Vec::new();
+            add_fields(payload, &mut label, &mut children);
+            (label, children)
+        }
+        // Estructura con campos propios y su rango (`Param`)
+        Value::Object(map) if map.contains_key("span") => {
+            let mut label = String::new();
+            let mut children = Vec::new();
+            add_fields(value, &mut label, &mut children);
+            children.retain(|(name, _)| name != "span");
+            let span = &map["span"];
+            label.push_str(&format!(" [{}..{}]", span["start"], span["end"]));
+            (label.trim_start().to_string(), children)
+        }
+        Value::Object(_) | Value::Array(_) => {
+            let mut label = String::new();
+            let mut children = Vec::new();
+            add_fields(value, &mut label, &mut children);
+            (label.trim_start().to_string(), children)
+        }
+        // Variante sin datos (`"Break"`)
+        Value::String(text) => (text.clone(), Vec::new()),
+        _ => (value.to_string(), Vec::new()),
+    }
+}
+
+/// Reparte los campos de `payload`: los simples se agregan a `label` y el
+/// resto queda como hijo. Un `None` (`null`) no se muestra.
+fn add_fields<'a>(payload: &'a Value, label: &mut String, children: &mut Vec<(String, &'a Value)>) {
+    match payload {
+        // Variante que envuelve un solo nodo: `Grouping(expr)`
+        node if is_node(node) => children.push((String::new(), node)),
+        Value::Object(fields) => {
+            for (name, value) in fields {
+                match value {
+                    Value::Null => {}
+                    Value::Array(items) if items.is_empty() => label.push_str(&format!(" {name}=[]")),
+                    Value::Array(items) if items.iter().all(is_simple) => {
+                        label.push_str(&format!(" {name}={value}"));
+                    }
+                    Value::Array(items) => {
+                        children.extend(items.iter().map(|item| (name.clone(), item)));
+                    }
+                    Value::String(_) | Value::Number(_) | Value::Bool(_) => {
+                        label.push_str(&format!(" {name}={value}"));
+                    }
+                    Value::Object(_) => children.push((name.clone(), value)),
+                }
+            }
+        }
+        // Variante con varios valores sin nombre: `BuiltinCall("print", args)`;
+        // los elementos de una lista van directo como hijos
+        Value::Array(items) => {
+            for item in items {
+                if is_simple(item) {
+                    label.push(' ');
+                    label.push_str(&item.to_string());
+                } else if let Value::Array(nested) = item {
+                    children.extend(nested.iter().map(|item| (String::new(), item)));
+                } else {
+                    children.push((String::new(), item));
+                }
+            }
+        }
+        Value::Null => {}
+        value => {
+            label.push(' ');
+            label.push_str(&value.to_string());
+        }
+    }
+}
+
+/// Indica si el valor es un nodo del AST (`Statement` o `Expression`).
+fn is_node(value: &Value) -> bool {
+    matches!(value, Value::Object(map) if map.len() == 2 && map.contains_key("kind") && map.contains_key("span"))
+}
+
+/// Indica si el valor se escribe en la línea del nodo.
+fn is_simple(value: &Value) -> bool {
+    matches!(value, Value::String(_) | Value::Number(_) | Value::Bool(_) | Value::Null)
+}
@@ -0,0 +1,71 @@
+use crate::grammar::Type;
+use crate::span::Span;
+use serde::Serialize;
+use std::fmt;
+
+/// Anotación de tipo escrita en el código, como en `let nombre: string`,
+/// `fn total(precios: list<float>) -> float` o `let jefe: Persona?`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TypeExpr {
+    /// Qué tipo es y sus parámetros.
+    pub kind: TypeExprKind,
+    /// Rango de bytes que cubre la anotación completa.
+    pub span: Span,
+}
+
+impl TypeExpr {
+    /// Crea un nodo de tipo.
+    pub fn new(kind: TypeExprKind, span: Span) -> Self {
+        TypeExpr { kind, span }
+    }
+}
+
+/// Las formas que puede tener una anotación de tipo.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum TypeExprKind {
+    /// Tipo integrado del lenguaje: `int`, `float`, `string`, `bool`,
+    /// `null`, `list`, `matrix` o `map`.
+    Builtin(Type),
+
+    /// Tipo definido por el usuario con `type`, por ejemplo: `Punto`.
+    Named(String),
+
+    /// Tipo con parámetros: `list<int>`, `map<string, float>`.
+    Generic {
+        /// Tipo que recibe los parámetros (`list`, `map`, `Caja`, ...).
+        base: Box<TypeExpr>,
+        /// Parámetros entre `<` y `>`, en orden.
+        args: Vec<TypeExpr>,
+    },
+
+    /// Tipo que además acepta `none`: `int?`, `Persona?`.
+    Nullable(Box<TypeExpr>),
+}
+
+/// Muestra la anotación como se escribe en el código.
+///
+/// ```
+/// # use lemon_core::ast::{StatementKind, TypeExpr};
+/// let statements = lemon_core::parse("let tabla: map<string, list<int>>?\n").unwrap();
+/// let StatementKind::Variable { ty: Some(ty), .. } = &statements[0].kind else { panic!() };
+/// assert_eq!(ty.to_string(), "map<string, list<int>>?");
+/// ```
+impl fmt::Display for TypeExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            TypeExprKind::Builtin(ty) => write!(f, "{}", ty.as_str()),
+            TypeExprKind::Named(name) => write!(f, "{}", name),
+            TypeExprKind::Generic { base, args } => {
+                write!(f, "{}<", base)?;
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", arg)?;
+                }
+                write!(f, ">")
+            }
+            TypeExprKind::Nullable(inner) => write!(f, "{}?", inner),
+        }
+    }
+}
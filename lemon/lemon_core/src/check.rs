@@ -0,0 +1,647 @@
+// ============================
+// check.rs — Comprobación de tipos estática
+// ============================
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::ast::{
+    BinaryOp, Expression, ExpressionKind, InterpolationPart, Literal, Param, Statement, StatementKind, TypeExpr,
+    TypeExprKind, UnaryOp,
+};
+use crate::diagnostic::{codes, Diagnostic};
+use crate::eval::builtins;
+use crate::grammar::Type;
+use crate::i18n::tr;
+use crate::span::Span;
+
+// ============================
+// 📦 Tipos estáticos
+// ============================
+
+/// Tipo de una expresión según el comprobador.
+///
+/// `Any` es el tipo dinámico: lo tiene todo lo que no está anotado ni se
+/// puede deducir, y es compatible con cualquier tipo en ambos sentidos. Así
+/// el código sin anotaciones sigue funcionando igual que antes.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Ty {
+    /// Tipo dinámico: cualquier valor.
+    Any,
+    /// `int`
+    Int,
+    /// `float`
+    Float,
+    /// `string`
+    String,
+    /// `bool`
+    Bool,
+    /// `null`: solo el valor `none`.
+    Null,
+    /// `list<T>`
+    List(Box<Ty>),
+    /// `matrix<T>`
+    Matrix(Box<Ty>),
+    /// `map<K, V>`
+    Map(Box<Ty>, Box<Ty>),
+    /// Tipo definido con `type`; se compara por nombre.
+    Named(String),
+    /// `T?`: un `T` o `none`.
+    Nullable(Box<Ty>),
+    /// Función. `params` en `None` acepta cualquier número de argumentos (`print`).
+    Function { params: Option<Vec<Ty>>, ret: Box<Ty> },
+}
+
+impl Ty {
+    /// `T?`, sin anidar (`int??` es `int?`) y sin efecto sobre `any` y `null`.
+    pub fn nullable(self) -> Ty {
+        match self {
+            Ty::Any | Ty::Null | Ty::Nullable(_) => self,
+            ty => Ty::Nullable(Box::new(ty)),
+        }
+    }
+
+    /// Indica si un valor de tipo `other` se puede usar donde se espera `self`.
+    ///
+    /// ```
+    /// # use lemon_core::check::Ty;
+    /// assert!(Ty::Float.accepts(&Ty::Int));
+    /// assert!(Ty::Int.nullable().accepts(&Ty::Null));
+    /// assert!(Ty::Int.accepts(&Ty::Any));
+    /// assert!(!Ty::Int.accepts(&Ty::String));
+    /// assert!(!Ty::Int.accepts(&Ty::Int.nullable()));
+    /// ```
+    pub fn accepts(&self, other: &Ty) -> bool {
+        match (self, other) {
+            (Ty::Any, _) | (_, Ty::Any) => true,
+            // Los enteros y decimales comparten el mismo valor numérico en ejecución
+            (Ty::Float, Ty::Int) => true,
+            (Ty::Nullable(_), Ty::Null) => true,
+            (Ty::Nullable(a), Ty::Nullable(b)) => a.accepts(b),
+            (Ty::Nullable(a), b) => a.accepts(b),
+            (Ty::List(a), Ty::List(b)) | (Ty::Matrix(a), Ty::Matrix(b)) => a.accepts(b),
+            (Ty::Map(ka, va), Ty::Map(kb, vb)) => ka.accepts(kb) && va.accepts(vb),
+            (Ty::Function { params: pa, ret: ra }, Ty::Function { params: pb, ret: rb }) => {
+                let params = match (pa, pb) {
+                    (Some(pa), Some(pb)) => pa.len() == pb.len() && pa.iter().zip(pb).all(|(a, b)| b.accepts(a)),
+                    _ => true,
+                };
+                params && ra.accepts(rb)
+            }
+            (a, b) => a == b,
+        }
+    }
+
+    /// Tipo que cubre a los dos, como el de `c ? a : b`: el más amplio si uno
+    /// acepta al otro, `T?` si uno es `null` y `any` en otro caso.
+    fn join(self, other: Ty) -> Ty {
+        match (self, other) {
+            (Ty::Any, _) | (_, Ty::Any) => Ty::Any,
+            (Ty::Null, ty) | (ty, Ty::Null) => ty.nullable(),
+            (a, b) if a.accepts(&b) => a,
+            (a, b) if b.accepts(&a) => b,
+            _ => Ty::Any,
+        }
+    }
+
+    fn is_numeric(&self) -> bool {
+        matches!(self, Ty::Int | Ty::Float)
+    }
+}
+
+impl fmt::Display for Ty {
+    /// Muestra el tipo como se anota en el código (`list<int>`, `Persona?`).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Ty::Any => write!(f, "any"),
+            Ty::Int => write!(f, "int"),
+            Ty::Float => write!(f, "float"),
+            Ty::String => write!(f, "string"),
+            Ty::Bool => write!(f, "bool"),
+            Ty::Null => write!(f, "null"),
+            Ty::List(item) => write!(f, "list<{}>", item),
+            Ty::Matrix(item) => write!(f, "matrix<{}>", item),
+            Ty::Map(key, value) => write!(f, "map<{}, {}>", key, value),
+            Ty::Named(name) => write!(f, "{}", name),
+            Ty::Nullable(inner) => write!(f, "{}?", inner),
+            Ty::Function { params, ret } => {
+                match params {
+                    Some(params) => {
+                        let params: Vec<String> = params.iter().map(Ty::to_string).collect();
+                        write!(f, "fn({})", params.join(", "))?;
+                    }
+                    None => write!(f, "fn(...)")?,
+                }
+                write!(f, " -> {}", ret)
+            }
+        }
+    }
+}
+
+/// Tipo que representa una anotación. Los parámetros de tipo que faltan son `any`.
+fn lower(ty: &TypeExpr) -> Ty {
+    match &ty.kind {
+        TypeExprKind::Builtin(builtin) => lower_builtin(*builtin, &[]),
+        TypeExprKind::Named(name) => Ty::Named(name.clone()),
+        TypeExprKind::Generic { base, args } => match base.kind {
+            TypeExprKind::Builtin(builtin) => lower_builtin(builtin, args),
+            _ => Ty::Named(ty.to_string()),
+        },
+        TypeExprKind::Nullable(inner) => lower(inner).nullable(),
+    }
+}
+
+fn lower_builtin(builtin: Type, args: &[TypeExpr]) -> Ty {
+    let arg = |i: usize| Box::new(args.get(i).map_or(Ty::Any, lower));
+    match builtin {
+        Type::Int => Ty::Int,
+        Type::Float => Ty::Float,
+        Type::String => Ty::String,
+        Type::Bool => Ty::Bool,
+        Type::Null => Ty::Null,
+        Type::List => Ty::List(arg(0)),
+        Type::Matrix => Ty::Matrix(arg(0)),
+        Type::Map => Ty::Map(arg(0), arg(1)),
+    }
+}
+
+/// Cuántos parámetros de tipo lleva un tipo integrado (`map<K, V>` lleva dos).
+fn type_params(builtin: Type) -> usize {
+    match builtin {
+        Type::List | Type::Matrix => 1,
+        Type::Map => 2,
+        _ => 0,
+    }
+}
+
+/// Tipo de una función declarada: los parámetros y el retorno sin anotar son `any`.
+fn signature(params: &[Param], return_type: Option<&TypeExpr>) -> Ty {
+    Ty::Function {
+        params: Some(params.iter().map(|param| param.ty.as_ref().map_or(Ty::Any, lower)).collect()),
+        ret: Box::new(return_type.map_or(Ty::Any, lower)),
+    }
+}
+
+// ============================
+// 🔎 Comprobador
+// ============================
+
+/// Variable visible en un ámbito.
+struct Binding {
+    ty: Ty,
+    /// Rango de la anotación, si el tipo se escribió en vez de deducirse.
+    declared: Option<Span>,
+}
+
+/// Recorre el AST sin ejecutarlo, deduce el tipo de cada expresión y anota
+/// las incompatibilidades como diagnósticos.
+struct Checker {
+    scopes: Vec<HashMap<String, Binding>>,
+    /// Tipo de retorno anotado de cada función que se está comprobando, con su rango.
+    returns: Vec<Option<(Ty, Span)>>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Checker {
+    fn new() -> Self {
+        // Las funciones integradas, con las firmas de `eval::builtins`
+        let globals = builtins()
+            .into_iter()
+            .map(|builtin| {
+                let ty = Ty::Function { params: builtin.params, ret: Box::new(builtin.ret) };
+                (builtin.name.to_string(), Binding { ty, declared: None })
+            })
+            .collect();
+        Checker { scopes: vec![globals], returns: Vec::new(), diagnostics: Vec::new() }
+    }
+
+    // ========================
+    // Ámbitos
+    // ========================
+
+    fn declare(&mut self, name: &str, ty: Ty, declared: Option<Span>) {
+        let scope = self.scopes.last_mut().expect("siempre hay un ámbito global");
+        scope.insert(name.to_string(), Binding { ty, declared });
+    }
+
+    fn lookup(&mut self, name: &str) -> Option<&mut Binding> {
+        self.scopes.iter_mut().rev().find_map(|scope| scope.get_mut(name))
+    }
+
+    /// Ejecuta `f` en un ámbito nuevo, que se descarta al terminar.
+    fn scoped(&mut self, f: impl FnOnce(&mut Self)) {
+        self.scopes.push(HashMap::new());
+        f(self);
+        self.scopes.pop();
+    }
+
+    // ========================
+    // Instrucciones
+    // ========================
+
+    /// Comprueba un bloque. Las funciones con nombre se declaran antes, así
+    /// que una función puede llamar a otra definida más abajo.
+    fn block(&mut self, statements: &[Statement]) {
+        for stmt in statements {
+            if let StatementKind::Function { name: Some(name), params, return_type, .. } = &stmt.kind {
+                self.declare(name, signature(params, return_type.as_ref()), None);
+            }
+        }
+        for stmt in statements {
+            self.statement(stmt);
+        }
+    }
+
+    fn statement(&mut self, stmt: &Statement) {
+        match &stmt.kind {
+            StatementKind::Let { name, ty, value, .. } => self.bind(name, ty.as_ref(), Some(value)),
+            StatementKind::Variable { name, ty, initializer, .. } => self.bind(name, ty.as_ref(), initializer.as_ref()),
+
+            StatementKind::Function { name, params, return_type, body, .. } => {
+                for ty in params.iter().filter_map(|param| param.ty.as_ref()).chain(return_type) {
+                    self.check_type_args(ty);
+                }
+                if let Some(name) = name {
+                    self.declare(name, signature(params, return_type.as_ref()), None);
+                }
+                self.scoped(|checker| {
+                    for param in params {
+                        let ty = param.ty.as_ref().map_or(Ty::Any, lower);
+                        checker.declare(&param.name, ty, param.ty.as_ref().map(|ty| ty.span));
+                    }
+                    checker.returns.push(return_type.as_ref().map(|ty| (lower(ty), ty.span)));
+                    checker.block(body);
+                    checker.returns.pop();
+                });
+            }
+
+            StatementKind::If { condition, then_branch, else_branch } => {
+                self.condition(condition);
+                self.statement(then_branch);
+                if let Some(else_branch) = else_branch {
+                    self.statement(else_branch);
+                }
+            }
+
+            StatementKind::While { condition, body } => {
+                self.condition(condition);
+                self.statement(body);
+            }
+
+            StatementKind::DoWhile { body, condition } => {
+                self.statement(body);
+                self.condition(condition);
+            }
+
+            StatementKind::ForCStyle { init, condition, increment, body } => self.scoped(|checker| {
+                checker.statement(init);
+                checker.condition(condition);
+                checker.statement(body);
+                checker.expression(increment);
+            }),
+
+            StatementKind::ForIn { variable, iterable, body } => {
+                let item = match self.expression(iterable) {
+                    Ty::List(item) => *item,
+                    Ty::Matrix(item) => Ty::List(item),
+                    Ty::Map(key, _) => *key,
+                    Ty::String => Ty::String,
+                    Ty::Any => Ty::Any,
+                    other => {
+                        self.diagnostics.push(
+                            Diagnostic::error(codes::NOT_ITERABLE_TYPE, &tr(codes::NOT_ITERABLE_TYPE, &[&other]))
+                                .with_span(iterable.span),
+                        );
+                        Ty::Any
+                    }
+                };
+                self.scoped(|checker| {
+                    checker.declare(variable, item, None);
+                    checker.statement(body);
+                });
+            }
+
+            StatementKind::Return(value) => {
+                let ty = value.as_ref().map_or(Ty::Null, |value| self.expression(value));
+                if let Some(Some((expected, declared))) = self.returns.last().cloned() {
+                    let span = value.as_ref().map_or(stmt.span, |value| value.span);
+                    self.expect(&expected, &ty, span, Some(declared));
+                }
+            }
+
+            StatementKind::Expr(expr) | StatementKind::Expression(expr) => {
+                self.expression(expr);
+            }
+
+            StatementKind::Block(statements) => self.scoped(|checker| checker.block(statements)),
+
+            StatementKind::Try { body, error, handler } => {
+                self.statement(body);
+                if let Some(handler) = handler {
+                    self.scoped(|checker| {
+                        if let Some(name) = error {
+                            checker.declare(name, Ty::String, None);
+                        }
+                        checker.statement(handler);
+                    });
+                }
+            }
+
+            StatementKind::BuiltinCall(_, args) => {
+                for arg in args {
+                    self.expression(arg);
+                }
+            }
+
+            StatementKind::UiElement { argument, attributes, body, .. } => {
+                for expr in argument.iter().chain(attributes.iter().map(|(_, value)| value)) {
+                    self.expression(expr);
+                }
+                if let Some(body) = body {
+                    self.statement(body);
+                }
+            }
+
+            StatementKind::Delete(_)
+            | StatementKind::Break
+            | StatementKind::Continue
+            | StatementKind::End
+            | StatementKind::Ui => {}
+        }
+    }
+
+    /// Declara una variable con `let`. Sin anotación toma el tipo de su valor,
+    /// salvo `none`, que no dice nada del tipo que tendrá después.
+    fn bind(&mut self, name: &str, ty: Option<&TypeExpr>, value: Option<&Expression>) {
+        let found = value.map(|value| (self.expression(value), value.span));
+        match ty {
+            Some(annotation) => {
+                self.check_type_args(annotation);
+                let declared = lower(annotation);
+                if let Some((found, span)) = found {
+                    self.expect(&declared, &found, span, Some(annotation.span));
+                }
+                self.declare(name, declared, Some(annotation.span));
+            }
+            None => {
+                let ty = match found {
+                    Some((Ty::Null, _)) | None => Ty::Any,
+                    Some((ty, _)) => ty,
+                };
+                self.declare(name, ty, None);
+            }
+        }
+    }
+
+    /// Comprueba una condición. Cualquier valor sirve (se usa su veracidad,
+    /// como en `while n` o `if len(xs)`); solo se avisa cuando el resultado no
+    /// depende del valor: una función siempre es verdadera y `none`, falso.
+    fn condition(&mut self, condition: &Expression) {
+        let ty = self.expression(condition);
+        let note = match ty {
+            Ty::Function { .. } => "E0403.function",
+            Ty::Null => "E0403.null",
+            _ => return,
+        };
+        self.diagnostics.push(
+            Diagnostic::warning(codes::NON_BOOL_CONDITION, &tr(codes::NON_BOOL_CONDITION, &[&ty]))
+                .with_span(condition.span)
+                .with_note(&tr(note, &[])),
+        );
+    }
+
+    /// Informa los tipos integrados con un número incorrecto de parámetros (`list<int, int>`).
+    fn check_type_args(&mut self, ty: &TypeExpr) {
+        match &ty.kind {
+            TypeExprKind::Generic { base, args } => {
+                if let TypeExprKind::Builtin(builtin) = base.kind
+                    && args.len() != type_params(builtin)
+                {
+                    self.diagnostics.push(
+                        Diagnostic::error(
+                            codes::TYPE_ARGUMENTS,
+                            &tr(codes::TYPE_ARGUMENTS, &[&builtin.as_str(), &type_params(builtin), &args.len()]),
+                        )
+                        .with_span(ty.span),
+                    );
+                }
+                for arg in args {
+                    self.check_type_args(arg);
+                }
+            }
+            TypeExprKind::Nullable(inner) => self.check_type_args(inner),
+            TypeExprKind::Builtin(_) | TypeExprKind::Named(_) => {}
+        }
+    }
+
+    /// Informa si un valor de tipo `found` no cabe donde se espera `expected`.
+    /// `declared` señala la anotación que fijó el tipo esperado.
+    fn expect(&mut self, expected: &Ty, found: &Ty, span: Span, declared: Option<Span>) {
+        if expected.accepts(found) {
+            return;
+        }
+        let mut diagnostic = Diagnostic::error(codes::MISMATCHED_TYPES, &tr(codes::MISMATCHED_TYPES, &[expected, found]))
+            .with_label(span, &tr("E0401.label", &[found]));
+        if let Some(declared) = declared {
+            diagnostic = diagnostic.with_secondary(declared, &tr("E0401.declared", &[]));
+        }
+        self.diagnostics.push(diagnostic);
+    }
+
+    /// Comprueba que un valor de tipo `ty` se pueda asignar a `variable`.
+    fn assign(&mut self, variable: &str, ty: &Ty, span: Span) {
+        let Some(binding) = self.lookup(variable) else { return };
+        match binding.declared {
+            Some(declared) => {
+                let expected = binding.ty.clone();
+                self.expect(&expected, ty, span, Some(declared));
+            }
+            // Sin anotación la variable puede cambiar de tipo: desde
+            // aquí se trata como dinámica
+            None if !binding.ty.accepts(ty) => binding.ty = Ty::Any,
+            None => {}
+        }
+    }
+
+    /// Tipo de `left op right`; si el operador no admite esos operandos lo
+    /// informa en `span` y devuelve `any`.
+    fn operands(&mut self, op: &BinaryOp, left: &Expression, left_ty: &Ty, right: &Expression, right_ty: &Ty, span: Span) -> Ty {
+        binary(op, left_ty, right_ty).unwrap_or_else(|| {
+            self.diagnostics.push(
+                Diagnostic::error(codes::INVALID_OPERANDS, &tr(codes::INVALID_OPERANDS, &[&op.as_str(), left_ty, right_ty]))
+                    .with_span(span)
+                    .with_secondary(left.span, &tr("E0401.label", &[left_ty]))
+                    .with_secondary(right.span, &tr("E0401.label", &[right_ty])),
+            );
+            Ty::Any
+        })
+    }
+
+    // ========================
+    // Expresiones
+    // ========================
+
+    fn expression(&mut self, expr: &Expression) -> Ty {
+        match &expr.kind {
+            ExpressionKind::Integer(_) | ExpressionKind::Literal(Literal::Integer(_)) => Ty::Int,
+            ExpressionKind::Float(_) | ExpressionKind::Literal(Literal::Float(_)) => Ty::Float,
+            ExpressionKind::String(_) | ExpressionKind::Literal(Literal::String(_)) => Ty::String,
+            ExpressionKind::Boolean(_) | ExpressionKind::Literal(Literal::Boolean(_)) => Ty::Bool,
+            ExpressionKind::None | ExpressionKind::Literal(Literal::None) => Ty::Null,
+            ExpressionKind::Interpolated(parts) => {
+                for part in parts {
+                    if let InterpolationPart::Expression(expr) = part {
+                        self.expression(expr);
+                    }
+                }
+                Ty::String
+            }
+
+            // Una variable desconocida es `any`: su ausencia la informa `resolve`
+            ExpressionKind::Variable(name) => self.lookup(name).map_or(Ty::Any, |binding| binding.ty.clone()),
+
+            ExpressionKind::Grouping(inner) => self.expression(inner),
+
+            ExpressionKind::Assign { variable, value } => {
+                let ty = self.expression(value);
+                self.assign(variable, &ty, value.span);
+                ty
+            }
+
+            ExpressionKind::CompoundAssign { target, op, value } => {
+                let target_ty = self.expression(target);
+                let value_ty = self.expression(value);
+                let ty = self.operands(op, target, &target_ty, value, &value_ty, expr.span);
+                if let ExpressionKind::Variable(variable) = &target.kind {
+                    self.assign(variable, &ty, expr.span);
+                }
+                ty
+            }
+
+            ExpressionKind::Unary { op, expr: operand } => {
+                let ty = self.expression(operand);
+                match op {
+                    UnaryOp::Not => Ty::Bool,
+                    UnaryOp::Neg if ty.is_numeric() || ty == Ty::Any => ty,
+                    UnaryOp::Neg => {
+                        self.diagnostics.push(
+                            Diagnostic::error(codes::INVALID_OPERANDS, &tr("E0402.negate", &[&ty]))
+                                .with_label(operand.span, &tr("E0401.label", &[&ty])),
+                        );
+                        Ty::Any
+                    }
+                }
+            }
+
+            ExpressionKind::Binary { left, op, right } => {
+                let left_ty = self.expression(left);
+                let right_ty = self.expression(right);
+                self.operands(op, left, &left_ty, right, &right_ty, expr.span)
+            }
+
+            ExpressionKind::Ternary { condition, then_branch, else_branch } => {
+                self.condition(condition);
+                let then_ty = self.expression(then_branch);
+                let else_ty = self.expression(else_branch);
+                then_ty.join(else_ty)
+            }
+
+            ExpressionKind::Call { function, args } => {
+                let callee = self.expression(function);
+                let arg_types: Vec<Ty> = args.iter().map(|arg| self.expression(arg)).collect();
+                match callee {
+                    Ty::Any => Ty::Any,
+                    Ty::Function { params: None, ret } => *ret,
+                    Ty::Function { params: Some(params), ret } => {
+                        if params.len() == args.len() {
+                            for ((param, arg), ty) in params.iter().zip(args).zip(&arg_types) {
+                                self.expect(param, ty, arg.span, None);
+                            }
+                        } else {
+                            let name = match &function.kind {
+                                ExpressionKind::Variable(name) => name.clone(),
+                                _ => Ty::Function { params: Some(params.clone()), ret: ret.clone() }.to_string(),
+                            };
+                            self.diagnostics.push(
+                                Diagnostic::error(
+                                    codes::ARGUMENT_COUNT,
+                                    &tr(codes::ARGUMENT_COUNT, &[&name, &params.len(), &args.len()]),
+                                )
+                                .with_span(expr.span),
+                            );
+                        }
+                        *ret
+                    }
+                    other => {
+                        self.diagnostics.push(
+                            Diagnostic::error(codes::NOT_CALLABLE_TYPE, &tr(codes::NOT_CALLABLE_TYPE, &[&other]))
+                                .with_span(function.span),
+                        );
+                        Ty::Any
+                    }
+                }
+            }
+
+            ExpressionKind::Get { object, name } => match (self.expression(object), name.as_str()) {
+                (Ty::List(_) | Ty::Matrix(_) | Ty::String, "length") => Ty::Int,
+                _ => Ty::Any,
+            },
+
+            ExpressionKind::Set { object, value, .. } => {
+                self.expression(object);
+                self.expression(value)
+            }
+        }
+    }
+}
+
+/// Tipo de `left op right`, o `None` si el operador no admite esos operandos.
+/// Sigue las reglas de `eval::binary`.
+fn binary(op: &BinaryOp, left: &Ty, right: &Ty) -> Option<Ty> {
+    use BinaryOp::*;
+
+    let comparable = (left.is_numeric() && right.is_numeric()) || (*left == Ty::String && *right == Ty::String);
+    match (op, left, right) {
+        (Eq | Neq, _, _) => Some(Ty::Bool),
+        // Devuelven el operando que decidió el resultado
+        (And | Or, _, _) => Some(left.clone().join(right.clone())),
+
+        (Add, Ty::String, _) | (Add, _, Ty::String) => Some(Ty::String),
+        (Add, Ty::List(a), Ty::List(b)) => Some(Ty::List(Box::new(a.as_ref().clone().join(b.as_ref().clone())))),
+        (Add | Sub | Mul | Mod | Pow, Ty::Int, Ty::Int) => Some(Ty::Int),
+        (Add | Sub | Mul | Div | Mod | Pow, _, _) if left.is_numeric() && right.is_numeric() => Some(Ty::Float),
+
+        (Gt | Gte | Lt | Lte, _, _) if comparable => Some(Ty::Bool),
+        (Gt | Gte | Lt | Lte, Ty::Any, _) | (Gt | Gte | Lt | Lte, _, Ty::Any) => Some(Ty::Bool),
+
+        (_, Ty::Any, _) | (_, _, Ty::Any) => Some(Ty::Any),
+        _ => None,
+    }
+}
+
+// ============================
+// 🚪 Punto de entrada
+// ============================
+
+/// Comprueba los tipos de un programa sin ejecutarlo y devuelve los
+/// diagnósticos encontrados.
+///
+/// Solo se comprueba lo que tiene tipo conocido: las anotaciones y lo que se
+/// deduce de los literales. Lo demás es `any` y se acepta en cualquier lugar.
+///
+/// ```
+/// # use lemon_core::check::check;
+/// let statements = lemon_core::parse("let edad: int = \"diez\"\nlet x = edad + 1\n").unwrap();
+/// let diagnostics = check(&statements);
+/// assert_eq!(diagnostics.len(), 1);
+/// assert_eq!(diagnostics[0].code, "E0401");
+///
+/// // Sin anotaciones no hay nada que comprobar
+/// let statements = lemon_core::parse("fn doble(x)\n  back x * 2\nend\ndoble(\"a\")\n").unwrap();
+/// assert!(check(&statements).is_empty());
+/// ```
+pub fn check(program: &[Statement]) -> Vec<Diagnostic> {
+    let mut checker = Checker::new();
+    checker.block(program);
+    checker.diagnostics
+}
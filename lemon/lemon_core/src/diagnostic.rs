@@ -0,0 +1,311 @@
+// ============================
+// diagnostic.rs — Diagnósticos estructurados
+// ============================
+
+use crate::i18n::tr;
+use crate::span::{SourceMap, Span};
+use serde::Serialize;
+use std::fmt;
+use unicode_segmentation::UnicodeSegmentation;
+
+// ============================
+// 🏷️ Códigos estables
+// ============================
+
+/// Códigos de diagnóstico. Son estables: las herramientas pueden filtrar o
+/// documentar errores por código sin depender del texto del mensaje.
+///
+/// - `E01xx`: errores léxicos
+/// - `E02xx`: errores de sintaxis
+/// - `E03xx`: errores en tiempo de ejecución
+/// - `E04xx`: errores de tipos, encontrados antes de ejecutar (ver `check`)
+/// - `E05xx`: errores de nombres, encontrados antes de ejecutar (ver `resolve`)
+pub mod codes {
+    // Léxicos
+    /// Comentario `/* ... */` sin cerrar.
+    pub const UNCLOSED_COMMENT: &str = "E0101";
+    /// Cadena sin comilla de cierre.
+    pub const UNCLOSED_STRING: &str = "E0102";
+    /// Secuencia de escape desconocida dentro de una cadena.
+    pub const INVALID_ESCAPE: &str = "E0103";
+    /// Carácter de control fuera de una cadena.
+    pub const ILLEGAL_CONTROL_CHARACTER: &str = "E0104";
+    /// Carácter que no pertenece al lenguaje.
+    pub const ILLEGAL_CHARACTER: &str = "E0105";
+    /// Palabra que no es identificador, número ni palabra reservada (`12abc`, `a$b`).
+    pub const UNKNOWN_TOKEN: &str = "E0106";
+    /// Número con caracteres que no le corresponden (`12abc`, `0b102`, `0x`).
+    pub const MALFORMED_NUMBER: &str = "E0107";
+    /// Número que no cabe en su tipo (entero de 64 bits o decimal finito).
+    pub const NUMBER_OUT_OF_RANGE: &str = "E0108";
+    /// Interpolación mal formada dentro de una cadena (`"{x"`, `"a } b"`).
+    pub const INVALID_INTERPOLATION: &str = "E0109";
+
+    // Sintaxis
+    /// Se esperaba un token concreto y se encontró otro.
+    pub const UNEXPECTED_TOKEN: &str = "E0201";
+    /// Se esperaba una expresión.
+    pub const EXPECTED_EXPRESSION: &str = "E0202";
+    /// El lado izquierdo de `=` no es asignable.
+    pub const INVALID_ASSIGNMENT_TARGET: &str = "E0203";
+    /// `end`, `elif`, `else` o `fail` sin un bloque abierto.
+    pub const UNEXPECTED_BLOCK_CLOSE: &str = "E0204";
+    /// Literal numérico que no se puede convertir.
+    pub const INVALID_NUMBER: &str = "E0205";
+    /// Identificador que es palabra reservada en el idioma al que se convierte el archivo.
+    pub const KEYWORD_CONFLICT: &str = "E0206";
+
+    // Ejecución
+    /// Uso de una variable que no existe.
+    pub const UNDEFINED_VARIABLE: &str = "E0301";
+    /// Operandos de tipo incompatible para un operador.
+    pub const TYPE_MISMATCH: &str = "E0302";
+    /// División o módulo entre cero.
+    pub const DIVISION_BY_ZERO: &str = "E0303";
+    /// Se intentó llamar algo que no es una función.
+    pub const NOT_CALLABLE: &str = "E0304";
+    /// Número de argumentos incorrecto.
+    pub const ARITY_MISMATCH: &str = "E0305";
+    /// Se superó la profundidad máxima de llamadas.
+    pub const STACK_OVERFLOW: &str = "E0306";
+    /// Propiedad inexistente o no asignable.
+    pub const INVALID_PROPERTY: &str = "E0307";
+    /// Valor que no se puede recorrer con `for ... in`.
+    pub const NOT_ITERABLE: &str = "E0308";
+    /// `break` o `continue` fuera de un bucle.
+    pub const LOOP_CONTROL_OUTSIDE_LOOP: &str = "E0309";
+    /// Llamada a una función integrada desconocida.
+    pub const UNKNOWN_BUILTIN: &str = "E0310";
+    /// Elemento de la interfaz gráfica, que el intérprete todavía no soporta.
+    pub const UI_UNSUPPORTED: &str = "E0311";
+    /// Declaración que no figura en la `Resolution` con que se ejecuta: la
+    /// resolución es de otro programa.
+    pub const UNRESOLVED_DECLARATION: &str = "E0312";
+
+    // Tipos
+    /// Valor de un tipo distinto al anotado (en `let`, argumentos, `back` o asignaciones).
+    pub const MISMATCHED_TYPES: &str = "E0401";
+    /// Operandos de tipo incompatible para un operador.
+    pub const INVALID_OPERANDS: &str = "E0402";
+    /// Condición que siempre vale lo mismo: una función o `none`
+    /// (advertencia). Los demás valores se aceptan por su veracidad.
+    pub const NON_BOOL_CONDITION: &str = "E0403";
+    /// Llamada con un número de argumentos distinto al de la función.
+    pub const ARGUMENT_COUNT: &str = "E0404";
+    /// Tipo integrado con un número incorrecto de parámetros de tipo (`list<int, int>`).
+    pub const TYPE_ARGUMENTS: &str = "E0405";
+    /// Llamada a un valor cuyo tipo no es una función.
+    pub const NOT_CALLABLE_TYPE: &str = "E0406";
+    /// `for ... in` sobre un valor cuyo tipo no se puede recorrer.
+    pub const NOT_ITERABLE_TYPE: &str = "E0407";
+
+    // Nombres
+    /// Uso de un nombre que no está declarado en ningún ámbito visible.
+    pub const UNDEFINED_NAME: &str = "E0501";
+    /// Nombre declarado dos veces en el mismo ámbito.
+    pub const DUPLICATE_DECLARATION: &str = "E0502";
+    /// Declaración que oculta otra de un ámbito exterior (advertencia).
+    pub const SHADOWED_NAME: &str = "E0503";
+    /// Asignación o `del` sobre una variable `sure`. Se detecta antes de
+    /// ejecutar y, si el programa se ejecuta igual (con `eval::evaluate`),
+    /// al ejecutar.
+    pub const SURE_BINDING: &str = "E0504";
+    /// Uso de una declaración marcada con `old` (advertencia, o error con
+    /// `--deny-old` en la línea de comandos).
+    pub const DEPRECATED_NAME: &str = "E0505";
+    /// Llamada escrita antes de que se declare una variable o función que la
+    /// función llamada usa, directamente o a través de otra (advertencia:
+    /// puede que la llamada no llegue a ejecutarse ahí).
+    pub const CALL_BEFORE_DECLARATION: &str = "E0506";
+}
+
+// ============================
+// 📦 Tipos
+// ============================
+
+/// Gravedad de un diagnóstico.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+impl fmt::Display for Severity {
+    /// Nombre de la gravedad en el idioma activo (ver `i18n`).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let key = match self {
+            Severity::Error => "severity.error",
+            Severity::Warning => "severity.warning",
+            Severity::Note => "severity.note",
+        };
+        write!(f, "{}", tr(key, &[]))
+    }
+}
+
+/// Un rango del código fuente con un texto explicativo opcional.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Label {
+    pub span: Span,
+    pub message: Option<String>,
+}
+
+/// Diagnóstico producido por el lexer, el parser o el evaluador.
+///
+/// El mensaje se traduce al crearlo, con el idioma activo en ese momento
+/// (ver `i18n`); el código no cambia entre idiomas.
+///
+/// La ubicación nunca va dentro del texto: está en `primary` (y en
+/// `secondary`), y se traduce a línea/columna con un `SourceMap`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: &'static str,       // Código estable, ver `codes`
+    pub message: String,          // Descripción principal
+    pub primary: Option<Label>,   // Rango principal (donde está el problema)
+    pub secondary: Vec<Label>,    // Rangos relacionados (p. ej. la declaración original)
+    pub notes: Vec<String>,       // Contexto adicional
+    pub help: Option<String>,     // Sugerencia para corregirlo
+}
+
+impl Diagnostic {
+    /// Crea un diagnóstico con la gravedad dada y sin ubicación.
+    pub fn new(severity: Severity, code: &'static str, message: &str) -> Self {
+        Diagnostic {
+            severity,
+            code,
+            message: message.to_string(),
+            primary: None,
+            secondary: Vec::new(),
+            notes: Vec::new(),
+            help: None,
+        }
+    }
+
+    /// Crea un error.
+    pub fn error(code: &'static str, message: &str) -> Self {
+        Self::new(Severity::Error, code, message)
+    }
+
+    /// Crea una advertencia.
+    pub fn warning(code: &'static str, message: &str) -> Self {
+        Self::new(Severity::Warning, code, message)
+    }
+
+    /// Fija el rango principal, sin texto.
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.primary = Some(Label { span, message: None });
+        self
+    }
+
+    /// Fija el rango principal con un texto explicativo.
+    pub fn with_label(mut self, span: Span, message: &str) -> Self {
+        self.primary = Some(Label { span, message: Some(message.to_string()) });
+        self
+    }
+
+    /// Agrega un rango secundario.
+    pub fn with_secondary(mut self, span: Span, message: &str) -> Self {
+        self.secondary.push(Label { span, message: Some(message.to_string()) });
+        self
+    }
+
+    /// Agrega una nota.
+    pub fn with_note(mut self, note: &str) -> Self {
+        self.notes.push(note.to_string());
+        self
+    }
+
+    /// Fija el texto de ayuda.
+    pub fn with_help(mut self, help: &str) -> Self {
+        self.help = Some(help.to_string());
+        self
+    }
+
+    /// Fija el rango principal solo si aún no tiene uno.
+    /// Permite que el nodo más interno que falló conserve su ubicación.
+    pub fn or_span(mut self, span: Span) -> Self {
+        self.primary.get_or_insert(Label { span, message: None });
+        self
+    }
+
+    /// Rango principal, si lo hay.
+    pub fn span(&self) -> Option<Span> {
+        self.primary.as_ref().map(|label| label.span)
+    }
+
+    /// Indica si el diagnóstico es un error (y no una advertencia o nota).
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+
+    /// Dibuja el diagnóstico con el fragmento de código y marcas `^^^`
+    /// debajo de cada rango (`^` para el principal, `-` para los secundarios).
+    pub fn render(&self, map: &SourceMap) -> String {
+        let mut out = format!("{}[{}]: {}\n", self.severity, self.code, self.message);
+
+        let mut labels: Vec<(&Label, char)> = Vec::new();
+        if let Some(primary) = &self.primary {
+            labels.push((primary, '^'));
+        }
+        labels.extend(self.secondary.iter().map(|label| (label, '-')));
+
+        let last_line = labels
+            .iter()
+            .map(|(label, _)| map.location(label.span.start).line)
+            .max()
+            .unwrap_or(1);
+        let gutter = last_line.to_string().len();
+        let pad = " ".repeat(gutter);
+
+        if let Some((first, _)) = labels.first() {
+            out.push_str(&format!("{}--> {}\n", pad, map.describe(first.span)));
+            out.push_str(&format!("{} |\n", pad));
+        }
+
+        // Un fragmento por línea, en orden; varias marcas pueden compartir línea.
+        labels.sort_by_key(|(label, _)| label.span.start);
+        let mut previous_line = None;
+        for (label, marker) in &labels {
+            let (start, end) = map.span_locations(label.span);
+            let text = map.line_text(start.line);
+
+            if previous_line != Some(start.line) {
+                out.push_str(&format!("{:>width$} | {}\n", start.line, text, width = gutter));
+                previous_line = Some(start.line);
+            }
+
+            // Un rango que sigue en otra línea se marca hasta el final de la primera.
+            let line_width = text.graphemes(true).count() + 1;
+            let end_column = if end.line == start.line { end.column } else { line_width };
+            let width = end_column.saturating_sub(start.column).max(1);
+            out.push_str(&format!(
+                "{} | {}{}",
+                pad,
+                " ".repeat(start.column - 1),
+                marker.to_string().repeat(width)
+            ));
+            if let Some(message) = &label.message {
+                out.push_str(&format!(" {}", message));
+            }
+            out.push('\n');
+        }
+
+        for note in &self.notes {
+            out.push_str(&format!("{} = {}: {}\n", pad, tr("render.note", &[]), note));
+        }
+        if let Some(help) = &self.help {
+            out.push_str(&format!("{} = {}: {}\n", pad, tr("render.help", &[]), help));
+        }
+        out
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[{}]: {}", self.severity, self.code, self.message)
+    }
+}
+
+impl std::error::Error for Diagnostic {}
@@ -0,0 +1,920 @@
+// ============================
+// eval.rs — Evaluador (intérprete de árbol)
+// ============================
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::rc::Rc;
+
+use crate::ast::{BinaryOp, Expression, ExpressionKind, InterpolationPart, Literal, NodeId, Statement, StatementKind, UnaryOp};
+use crate::check::Ty;
+use crate::diagnostic::{codes, Diagnostic};
+use crate::i18n::tr;
+use crate::resolve::{resolve, BindingId, Resolution};
+
+/// Profundidad máxima de llamadas anidadas antes de abortar la ejecución.
+/// Evita que una recursión infinita desborde la pila del intérprete.
+const MAX_CALL_DEPTH: usize = 256;
+
+// ============================
+// 📦 Valores en tiempo de ejecución
+// ============================
+
+/// Representa cualquier valor que puede producir un programa Yuka al ejecutarse.
+///
+/// Las listas y los mapas se comparten por referencia (como en la mayoría de
+/// lenguajes dinámicos): modificar una copia modifica el original.
+#[derive(Debug, Clone)]
+pub enum Value {
+    /// Número de punto flotante: `42`, `3.14`
+    Number(f64),
+    /// Cadena de texto: `"hola"`
+    String(String),
+    /// Booleano: `true` o `false`
+    Bool(bool),
+    /// Ausencia de valor: `none` / `null`
+    None,
+    /// Función definida por el usuario o integrada en el runtime.
+    Function(Rc<Function>),
+    /// Lista ordenada de valores.
+    List(Rc<RefCell<Vec<Value>>>),
+    /// Mapa de claves de texto a valores.
+    Map(Rc<RefCell<BTreeMap<String, Value>>>),
+}
+
+/// Tipo de las funciones nativas del runtime.
+pub type NativeFn = fn(&[Value]) -> Result<Value, Diagnostic>;
+
+/// Una función invocable desde Yuka.
+pub enum Function {
+    /// Función declarada con `fn`, junto con el entorno donde fue creada (closure).
+    User {
+        name: Option<String>,
+        params: Vec<BindingId>,
+        body: Vec<Statement>,
+        closure: Rc<RefCell<Environment>>,
+    },
+    /// Función integrada implementada en Rust.
+    /// `arity` en `None` indica que acepta cualquier número de argumentos.
+    Native {
+        name: &'static str,
+        arity: Option<usize>,
+        func: NativeFn,
+    },
+}
+
+impl Function {
+    /// Nombre de la función, o `"<anónima>"` si no tiene.
+    pub fn name(&self) -> &str {
+        match self {
+            Function::User { name, .. } => name.as_deref().unwrap_or("<anónima>"),
+            Function::Native { name, .. } => name,
+        }
+    }
+}
+
+impl fmt::Debug for Function {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<fn {}>", self.name())
+    }
+}
+
+impl Value {
+    /// Regla de veracidad: `false`, `none`, `0`, `""` y colecciones vacías son falsos.
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Value::Bool(b) => *b,
+            Value::None => false,
+            Value::Number(n) => *n != 0.0,
+            Value::String(s) => !s.is_empty(),
+            Value::List(items) => !items.borrow().is_empty(),
+            Value::Map(entries) => !entries.borrow().is_empty(),
+            Value::Function(_) => true,
+        }
+    }
+
+    /// Nombre del tipo del valor, usado en mensajes de error y en `type()`.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::Bool(_) => "bool",
+            Value::None => "none",
+            Value::Function(_) => "function",
+            Value::List(_) => "list",
+            Value::Map(_) => "map",
+        }
+    }
+}
+
+impl PartialEq for Value {
+    /// Igualdad estructural para datos; las funciones solo son iguales a sí mismas.
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value::Number(a), Value::Number(b)) => a == b,
+            (Value::String(a), Value::String(b)) => a == b,
+            (Value::Bool(a), Value::Bool(b)) => a == b,
+            (Value::None, Value::None) => true,
+            (Value::Function(a), Value::Function(b)) => Rc::ptr_eq(a, b),
+            (Value::List(a), Value::List(b)) => Rc::ptr_eq(a, b) || *a.borrow() == *b.borrow(),
+            (Value::Map(a), Value::Map(b)) => Rc::ptr_eq(a, b) || *a.borrow() == *b.borrow(),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Number(n) => {
+                if n.fract() == 0.0 && n.is_finite() && n.abs() < 1e15 {
+                    write!(f, "{}", *n as i64)
+                } else {
+                    write!(f, "{}", n)
+                }
+            }
+            Value::String(s) => write!(f, "{}", s),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::None => write!(f, "none"),
+            Value::Function(func) => write!(f, "<fn {}>", func.name()),
+            Value::List(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.borrow().iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+            Value::Map(entries) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in entries.borrow().iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: {}", key, value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+// ============================
+// 🗂️ Entornos (ámbitos léxicos)
+// ============================
+
+/// Un ámbito de variables. Cada bloque, función o bucle crea uno nuevo
+/// que apunta a su ámbito padre. Las variables se guardan por la
+/// declaración a la que `resolve` enlazó cada uso, no por su nombre.
+///
+/// ```
+/// # use lemon_core::eval::{Environment, Value};
+/// # use lemon_core::resolve::BindingId;
+/// # use std::{cell::RefCell, rc::Rc};
+/// let global = Rc::new(RefCell::new(Environment::new()));
+/// global.borrow_mut().define(BindingId(1), Value::Number(1.0));
+///
+/// // Un ámbito hijo ve las variables del padre y puede reasignarlas
+/// let mut local = Environment::with_parent(Rc::clone(&global));
+/// local.define(BindingId(2), Value::Bool(true));
+/// assert!(local.assign(BindingId(1), Value::Number(2.0)));
+/// assert_eq!(global.borrow().get(BindingId(1)), Some(Value::Number(2.0)));
+/// assert_eq!(global.borrow().get(BindingId(2)), None);
+/// ```
+#[derive(Debug, Default)]
+pub struct Environment {
+    values: HashMap<BindingId, Value>,
+    parent: Option<Rc<RefCell<Environment>>>,
+}
+
+impl Environment {
+    /// Crea el ámbito global (sin padre).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Crea un ámbito hijo de `parent`.
+    pub fn with_parent(parent: Rc<RefCell<Environment>>) -> Self {
+        Self { values: HashMap::new(), parent: Some(parent) }
+    }
+
+    /// Declara (o redeclara) una variable en este ámbito.
+    pub fn define(&mut self, binding: BindingId, value: Value) {
+        self.values.insert(binding, value);
+    }
+
+    /// Busca una variable subiendo por la cadena de ámbitos.
+    pub fn get(&self, binding: BindingId) -> Option<Value> {
+        match self.values.get(&binding) {
+            Some(value) => Some(value.clone()),
+            None => self.parent.as_ref().and_then(|p| p.borrow().get(binding)),
+        }
+    }
+
+    /// Reasigna una variable existente en el ámbito más cercano que la contenga.
+    /// Retorna `false` si la variable no fue declarada.
+    pub fn assign(&mut self, binding: BindingId, value: Value) -> bool {
+        if let Some(slot) = self.values.get_mut(&binding) {
+            *slot = value;
+            return true;
+        }
+        match &self.parent {
+            Some(parent) => parent.borrow_mut().assign(binding, value),
+            None => false,
+        }
+    }
+
+    /// Borra una variable del ámbito más cercano que la contenga.
+    /// Retorna `false` si la variable no fue declarada.
+    pub fn remove(&mut self, binding: BindingId) -> bool {
+        if self.values.remove(&binding).is_some() {
+            return true;
+        }
+        match &self.parent {
+            Some(parent) => parent.borrow_mut().remove(binding),
+            None => false,
+        }
+    }
+}
+
+// ============================
+// 🔁 Control de flujo
+// ============================
+
+/// Resultado de ejecutar una instrucción: continúa normalmente o
+/// interrumpe el flujo (`return`, `break`, `continue`).
+enum Flow {
+    Normal,
+    Return(Value),
+    Break,
+    Continue,
+}
+
+// ============================
+// ⚙️ Intérprete
+// ============================
+
+/// Intérprete de árbol: recorre el AST y ejecuta cada nodo.
+pub struct Interpreter {
+    globals: Rc<RefCell<Environment>>,
+    env: Rc<RefCell<Environment>>,
+    depth: usize,
+    /// Declaración de cada uso y de cada `let`, `fn`, parámetro, etc. del
+    /// programa que se ejecuta.
+    resolution: Resolution,
+}
+
+impl Interpreter {
+    /// Crea un intérprete para el programa que `resolution` describe, con las
+    /// funciones integradas ya registradas. Con la resolución de otro
+    /// programa, la ejecución falla con un error en vez de entrar en pánico.
+    ///
+    /// ```
+    /// # use lemon_core::eval::Interpreter;
+    /// # use lemon_core::resolve::resolve;
+    /// let program = lemon_core::parse("let x = 1\n").unwrap();
+    /// let other = lemon_core::parse("let x = 1\n").unwrap();
+    /// let error = Interpreter::new(resolve(&other)).run(&program).unwrap_err();
+    /// assert_eq!(error.code, "E0312");
+    /// ```
+    pub fn new(resolution: Resolution) -> Self {
+        let globals = Rc::new(RefCell::new(Environment::new()));
+        for builtin in builtins() {
+            if let Some(id) = resolution.builtin(builtin.name) {
+                let (name, arity, func) = (builtin.name, builtin.arity(), builtin.func);
+                globals.borrow_mut().define(id, Value::Function(Rc::new(Function::Native { name, arity, func })));
+            }
+        }
+        Self { env: Rc::clone(&globals), globals, depth: 0, resolution }
+    }
+
+    /// Ejecuta un programa completo.
+    ///
+    /// Retorna el valor de la última instrucción si es una expresión (o el
+    /// valor de un `return` en el nivel superior), o `Value::None`.
+    ///
+    /// ```
+    /// # use lemon_core::Value;
+    /// // `x = "s"` es una expresión, pero no la última: solo `print` muestra algo
+    /// assert_eq!(lemon_core::interpret("let x = 1\nx = \"s\"\nprint x\n"), Ok(Value::None));
+    /// assert_eq!(lemon_core::interpret("let x = 1\nx + 1\n"), Ok(Value::Number(2.0)));
+    /// ```
+    pub fn run(&mut self, program: &[Statement]) -> Result<Value, Diagnostic> {
+        let mut last = Value::None;
+        for stmt in program {
+            if let StatementKind::Expr(expr) | StatementKind::Expression(expr) = &stmt.kind {
+                last = self.evaluate(expr)?;
+                continue;
+            }
+            last = Value::None;
+            match self.execute(stmt)? {
+                Flow::Normal => {}
+                Flow::Return(value) => return Ok(value),
+                Flow::Break => {
+                    return Err(Diagnostic::error(codes::LOOP_CONTROL_OUTSIDE_LOOP, &tr("E0309.break", &[])).with_span(stmt.span))
+                }
+                Flow::Continue => {
+                    return Err(Diagnostic::error(codes::LOOP_CONTROL_OUTSIDE_LOOP, &tr("E0309.continue", &[])).with_span(stmt.span))
+                }
+            }
+        }
+        Ok(last)
+    }
+
+    // ========================
+    // Instrucciones
+    // ========================
+
+    /// Ejecuta una instrucción. Si falla sin ubicación, el error toma el rango de la instrucción.
+    fn execute(&mut self, stmt: &Statement) -> Result<Flow, Diagnostic> {
+        self.execute_kind(stmt).map_err(|e| e.or_span(stmt.span))
+    }
+
+    fn execute_kind(&mut self, stmt: &Statement) -> Result<Flow, Diagnostic> {
+        match &stmt.kind {
+            // Los tipos anotados no se comprueban al ejecutar
+            StatementKind::Let { name, value, .. } => {
+                let value = self.evaluate(value)?;
+                let id = self.declared(stmt.id, name)?;
+                self.env.borrow_mut().define(id, value);
+                Ok(Flow::Normal)
+            }
+
+            StatementKind::Delete(name) => {
+                let id = self.used(stmt.id, "E0301.delete", name)?;
+                self.check_not_sure(id, "E0504.delete")?;
+                if self.env.borrow_mut().remove(id) {
+                    Ok(Flow::Normal)
+                } else {
+                    Err(undefined("E0301.delete", name))
+                }
+            }
+
+            StatementKind::Variable { name, initializer, .. } => {
+                let value = match initializer {
+                    Some(expr) => self.evaluate(expr)?,
+                    None => Value::None,
+                };
+                let id = self.declared(stmt.id, name)?;
+                self.env.borrow_mut().define(id, value);
+                Ok(Flow::Normal)
+            }
+
+            StatementKind::Function { name, params, body, .. } => {
+                let function = Value::Function(Rc::new(Function::User {
+                    name: name.clone(),
+                    params: params.iter().map(|param| self.declared(param.id, &param.name)).collect::<Result<_, _>>()?,
+                    body: body.clone(),
+                    closure: Rc::clone(&self.env),
+                }));
+                if let Some(name) = name {
+                    let id = self.declared(stmt.id, name)?;
+                    self.env.borrow_mut().define(id, function);
+                }
+                Ok(Flow::Normal)
+            }
+
+            StatementKind::If { condition, then_branch, else_branch } => {
+                if self.evaluate(condition)?.is_truthy() {
+                    self.execute(then_branch)
+                } else if let Some(else_branch) = else_branch {
+                    self.execute(else_branch)
+                } else {
+                    Ok(Flow::Normal)
+                }
+            }
+
+            StatementKind::While { condition, body } => {
+                while self.evaluate(condition)?.is_truthy() {
+                    match self.execute(body)? {
+                        Flow::Break => break,
+                        Flow::Return(value) => return Ok(Flow::Return(value)),
+                        Flow::Normal | Flow::Continue => {}
+                    }
+                }
+                Ok(Flow::Normal)
+            }
+
+            StatementKind::DoWhile { body, condition } => {
+                loop {
+                    match self.execute(body)? {
+                        Flow::Break => break,
+                        Flow::Return(value) => return Ok(Flow::Return(value)),
+                        Flow::Normal | Flow::Continue => {}
+                    }
+                    if !self.evaluate(condition)?.is_truthy() {
+                        break;
+                    }
+                }
+                Ok(Flow::Normal)
+            }
+
+            StatementKind::ForCStyle { init, condition, increment, body } => {
+                let scope = Environment::with_parent(Rc::clone(&self.env));
+                self.scoped(scope, |interp| {
+                    interp.execute(init)?;
+                    while interp.evaluate(condition)?.is_truthy() {
+                        match interp.execute(body)? {
+                            Flow::Break => break,
+                            Flow::Return(value) => return Ok(Flow::Return(value)),
+                            Flow::Normal | Flow::Continue => {}
+                        }
+                        interp.evaluate(increment)?;
+                    }
+                    Ok(Flow::Normal)
+                })
+            }
+
+            StatementKind::ForIn { variable, iterable, body } => {
+                let items = iterate(&self.evaluate(iterable)?)?;
+                let variable = self.declared(stmt.id, variable)?;
+                for item in items {
+                    let mut scope = Environment::with_parent(Rc::clone(&self.env));
+                    scope.define(variable, item);
+                    match self.scoped(scope, |interp| interp.execute(body))? {
+                        Flow::Break => break,
+                        Flow::Return(value) => return Ok(Flow::Return(value)),
+                        Flow::Normal | Flow::Continue => {}
+                    }
+                }
+                Ok(Flow::Normal)
+            }
+
+            StatementKind::Return(value) => {
+                let value = match value {
+                    Some(expr) => self.evaluate(expr)?,
+                    None => Value::None,
+                };
+                Ok(Flow::Return(value))
+            }
+
+            StatementKind::Expr(expr) | StatementKind::Expression(expr) => {
+                self.evaluate(expr)?;
+                Ok(Flow::Normal)
+            }
+
+            StatementKind::Block(statements) => {
+                let scope = Environment::with_parent(Rc::clone(&self.env));
+                self.scoped(scope, |interp| interp.execute_block(statements))
+            }
+
+            StatementKind::Try { body, error, handler } => match self.execute(body) {
+                Ok(flow) => Ok(flow),
+                Err(e) => {
+                    let mut scope = Environment::with_parent(Rc::clone(&self.env));
+                    if let Some(name) = error {
+                        scope.define(self.declared(stmt.id, name)?, Value::String(e.message));
+                    }
+                    match handler {
+                        Some(handler) => self.scoped(scope, |interp| interp.execute(handler)),
+                        None => Ok(Flow::Normal),
+                    }
+                }
+            },
+
+            StatementKind::Break => Ok(Flow::Break),
+            StatementKind::Continue => Ok(Flow::Continue),
+            StatementKind::End => Ok(Flow::Normal),
+
+            StatementKind::BuiltinCall(name, args) => {
+                let callee = self.resolution.builtin(name).and_then(|id| self.globals.borrow().get(id)).ok_or_else(|| {
+                    Diagnostic::error(codes::UNKNOWN_BUILTIN, &tr(codes::UNKNOWN_BUILTIN, &[name]))
+                })?;
+                let args = args.iter().map(|arg| self.evaluate(arg)).collect::<Result<Vec<_>, _>>()?;
+                self.call(&callee, args)?;
+                Ok(Flow::Normal)
+            }
+
+            StatementKind::Ui => Ok(Flow::Normal),
+            StatementKind::UiElement { element, .. } => {
+                Err(Diagnostic::error(codes::UI_UNSUPPORTED, &tr(codes::UI_UNSUPPORTED, &[&element.as_str()])))
+            }
+        }
+    }
+
+    /// Ejecuta una lista de instrucciones en el ámbito actual,
+    /// propagando cualquier interrupción de flujo.
+    fn execute_block(&mut self, statements: &[Statement]) -> Result<Flow, Diagnostic> {
+        for stmt in statements {
+            match self.execute(stmt)? {
+                Flow::Normal => {}
+                flow => return Ok(flow),
+            }
+        }
+        Ok(Flow::Normal)
+    }
+
+    /// Ejecuta `f` con `scope` como ámbito actual y restaura el anterior al terminar,
+    /// incluso si `f` falla.
+    fn scoped<T>(
+        &mut self,
+        scope: Environment,
+        f: impl FnOnce(&mut Self) -> Result<T, Diagnostic>,
+    ) -> Result<T, Diagnostic> {
+        let previous = std::mem::replace(&mut self.env, Rc::new(RefCell::new(scope)));
+        let result = f(self);
+        self.env = previous;
+        result
+    }
+
+    // ========================
+    // Expresiones
+    // ========================
+
+    /// Evalúa una expresión. Si falla sin ubicación, el error toma el rango de la
+    /// expresión, de modo que apunta al nodo más interno que falló.
+    fn evaluate(&mut self, expr: &Expression) -> Result<Value, Diagnostic> {
+        self.evaluate_kind(expr).map_err(|e| e.or_span(expr.span))
+    }
+
+    fn evaluate_kind(&mut self, expr: &Expression) -> Result<Value, Diagnostic> {
+        match &expr.kind {
+            // Los enteros y decimales comparten el mismo valor numérico en ejecución
+            ExpressionKind::Integer(n) => Ok(Value::Number(*n as f64)),
+            ExpressionKind::Float(n) => Ok(Value::Number(*n)),
+            ExpressionKind::String(s) => Ok(Value::String(s.clone())),
+            ExpressionKind::Interpolated(parts) => {
+                // Cada expresión se muestra igual que con `print`
+                let mut text = String::new();
+                for part in parts {
+                    match part {
+                        InterpolationPart::Literal(s) => text.push_str(s),
+                        InterpolationPart::Expression(expr) => text.push_str(&self.evaluate(expr)?.to_string()),
+                    }
+                }
+                Ok(Value::String(text))
+            }
+            ExpressionKind::Boolean(b) => Ok(Value::Bool(*b)),
+            ExpressionKind::None => Ok(Value::None),
+            ExpressionKind::Literal(literal) => Ok(match literal {
+                Literal::Integer(n) => Value::Number(*n as f64),
+                Literal::Float(n) => Value::Number(*n),
+                Literal::String(s) => Value::String(s.clone()),
+                Literal::Boolean(b) => Value::Bool(*b),
+                Literal::None => Value::None,
+            }),
+
+            ExpressionKind::Variable(name) => {
+                let id = self.used(expr.id, codes::UNDEFINED_VARIABLE, name)?;
+                self.env.borrow().get(id).ok_or_else(|| undefined(codes::UNDEFINED_VARIABLE, name))
+            }
+
+            ExpressionKind::Grouping(inner) => self.evaluate(inner),
+
+            ExpressionKind::Assign { variable, value } => {
+                let id = self.used(expr.id, "E0301.assign", variable)?;
+                self.check_not_sure(id, codes::SURE_BINDING)?;
+                let value = self.evaluate(value)?;
+                self.assign(id, variable, value)
+            }
+
+            // El objeto de una propiedad se evalúa una sola vez: en
+            // `a.b().c += 1`, `b` se llama una vez
+            ExpressionKind::CompoundAssign { target, op, value } => match &target.kind {
+                ExpressionKind::Variable(variable) => {
+                    let id = self.used(expr.id, codes::UNDEFINED_VARIABLE, variable)?;
+                    self.check_not_sure(id, codes::SURE_BINDING)?;
+                    let current = self.env.borrow().get(id).ok_or_else(|| undefined(codes::UNDEFINED_VARIABLE, variable))?;
+                    let value = binary(op, current, self.evaluate(value)?)?;
+                    self.assign(id, variable, value)
+                }
+                ExpressionKind::Get { object, name } => {
+                    let object = self.evaluate(object)?;
+                    let current = get_property(&object, name)?;
+                    let value = binary(op, current, self.evaluate(value)?)?;
+                    set_property(object, name, value)
+                }
+                // El parser solo crea asignaciones compuestas sobre variables y propiedades
+                _ => Err(Diagnostic::error(codes::INVALID_ASSIGNMENT_TARGET, &tr(codes::INVALID_ASSIGNMENT_TARGET, &[]))),
+            },
+
+            ExpressionKind::Unary { op, expr } => {
+                let value = self.evaluate(expr)?;
+                match op {
+                    UnaryOp::Not => Ok(Value::Bool(!value.is_truthy())),
+                    UnaryOp::Neg => match value {
+                        Value::Number(n) => Ok(Value::Number(-n)),
+                        other => Err(Diagnostic::error(codes::TYPE_MISMATCH, &tr("E0302.negate", &[&other.type_name()]))),
+                    },
+                }
+            }
+
+            ExpressionKind::Binary { left, op, right } => {
+                // Los operadores lógicos evalúan en cortocircuito
+                // y devuelven el operando que decidió el resultado.
+                match op {
+                    BinaryOp::And => {
+                        let left = self.evaluate(left)?;
+                        return if left.is_truthy() { self.evaluate(right) } else { Ok(left) };
+                    }
+                    BinaryOp::Or => {
+                        let left = self.evaluate(left)?;
+                        return if left.is_truthy() { Ok(left) } else { self.evaluate(right) };
+                    }
+                    _ => {}
+                }
+                let left = self.evaluate(left)?;
+                let right = self.evaluate(right)?;
+                binary(op, left, right)
+            }
+
+            ExpressionKind::Ternary { condition, then_branch, else_branch } => {
+                if self.evaluate(condition)?.is_truthy() {
+                    self.evaluate(then_branch)
+                } else {
+                    self.evaluate(else_branch)
+                }
+            }
+
+            ExpressionKind::Call { function, args } => {
+                let callee = self.evaluate(function)?;
+                let args = args
+                    .iter()
+                    .map(|arg| self.evaluate(arg))
+                    .collect::<Result<Vec<_>, _>>()?;
+                self.call(&callee, args)
+            }
+
+            ExpressionKind::Get { object, name } => {
+                let object = self.evaluate(object)?;
+                get_property(&object, name)
+            }
+
+            ExpressionKind::Set { object, name, value } => {
+                let object = self.evaluate(object)?;
+                let value = self.evaluate(value)?;
+                set_property(object, name, value)
+            }
+        }
+    }
+
+    /// Declaración que hace la instrucción o el parámetro `node`, que declara
+    /// `name`. `resolve` registra una para cada `let`, `fn`, parámetro,
+    /// `for ... in` y `fail (e)`; si falta, la resolución no es la de este
+    /// programa (por ejemplo, un AST armado aparte).
+    fn declared(&self, node: NodeId, name: &str) -> Result<BindingId, Diagnostic> {
+        self.resolution.declared(node).ok_or_else(|| {
+            Diagnostic::error(codes::UNRESOLVED_DECLARATION, &tr(codes::UNRESOLVED_DECLARATION, &[&name]))
+                .with_help(&tr("E0312.help", &[]))
+        })
+    }
+
+    /// Declaración a la que se refiere el uso `node` de `name`. Si `resolve`
+    /// no la encontró, falla con el mensaje `key`.
+    fn used(&self, node: NodeId, key: &str, name: &str) -> Result<BindingId, Diagnostic> {
+        self.resolution.resolved(node).ok_or_else(|| undefined(key, name))
+    }
+
+    /// Asigna a una variable ya declarada y devuelve el valor asignado.
+    fn assign(&mut self, id: BindingId, variable: &str, value: Value) -> Result<Value, Diagnostic> {
+        if self.env.borrow_mut().assign(id, value.clone()) {
+            Ok(value)
+        } else {
+            Err(undefined("E0301.assign", variable))
+        }
+    }
+
+    /// Falla si la declaración es `sure`. `key` es el mensaje: modificarla o
+    /// borrarla. Normalmente `resolve` ya lo informó antes de ejecutar.
+    fn check_not_sure(&self, id: BindingId, key: &str) -> Result<(), Diagnostic> {
+        let binding = self.resolution.binding(id);
+        if !binding.sure {
+            return Ok(());
+        }
+        let mut diagnostic = Diagnostic::error(codes::SURE_BINDING, &tr(key, &[&binding.name])).with_help(&tr("E0504.help", &[]));
+        if let Some(declared) = binding.span {
+            diagnostic = diagnostic.with_secondary(declared, &tr("E0504.declared", &[]));
+        }
+        Err(diagnostic)
+    }
+
+    /// Invoca una función (de usuario o nativa) con los argumentos ya evaluados.
+    fn call(&mut self, callee: &Value, args: Vec<Value>) -> Result<Value, Diagnostic> {
+        let function = match callee {
+            Value::Function(function) => Rc::clone(function),
+            other => {
+                return Err(Diagnostic::error(codes::NOT_CALLABLE, &tr(codes::NOT_CALLABLE, &[&other.type_name()])))
+            }
+        };
+
+        match &*function {
+            Function::Native { name, arity, func } => {
+                if let Some(arity) = arity {
+                    check_arity(name, *arity, args.len())?;
+                }
+                func(&args)
+            }
+
+            Function::User { name, params, body, closure } => {
+                check_arity(name.as_deref().unwrap_or("<anónima>"), params.len(), args.len())?;
+                if self.depth >= MAX_CALL_DEPTH {
+                    return Err(Diagnostic::error(
+                        codes::STACK_OVERFLOW,
+                        &tr(codes::STACK_OVERFLOW, &[]),
+                    )
+                    .with_help(&tr("E0306.help", &[])));
+                }
+
+                let mut scope = Environment::with_parent(Rc::clone(closure));
+                for (&param, arg) in params.iter().zip(args) {
+                    scope.define(param, arg);
+                }
+
+                self.depth += 1;
+                let result = self.scoped(scope, |interp| interp.execute_block(body));
+                self.depth -= 1;
+
+                match result? {
+                    Flow::Return(value) => Ok(value),
+                    Flow::Normal => Ok(Value::None),
+                    Flow::Break => Err(Diagnostic::error(codes::LOOP_CONTROL_OUTSIDE_LOOP, &tr("E0309.break", &[]))),
+                    Flow::Continue => Err(Diagnostic::error(codes::LOOP_CONTROL_OUTSIDE_LOOP, &tr("E0309.continue", &[]))),
+                }
+            }
+        }
+    }
+}
+
+// ============================
+// 🧮 Operaciones sobre valores
+// ============================
+
+/// Aplica un operador binario (no lógico) a dos valores ya evaluados.
+fn binary(op: &BinaryOp, left: Value, right: Value) -> Result<Value, Diagnostic> {
+    use Value::*;
+
+    match (op, left, right) {
+        (BinaryOp::Eq, l, r) => Ok(Bool(l == r)),
+        (BinaryOp::Neq, l, r) => Ok(Bool(l != r)),
+
+        (BinaryOp::Add, Number(a), Number(b)) => Ok(Number(a + b)),
+        (BinaryOp::Add, String(a), b) => Ok(String(format!("{}{}", a, b))),
+        (BinaryOp::Add, a, String(b)) => Ok(String(format!("{}{}", a, b))),
+        (BinaryOp::Add, List(a), List(b)) => {
+            let mut items = a.borrow().clone();
+            items.extend(b.borrow().iter().cloned());
+            Ok(List(Rc::new(RefCell::new(items))))
+        }
+
+        (BinaryOp::Sub, Number(a), Number(b)) => Ok(Number(a - b)),
+        (BinaryOp::Mul, Number(a), Number(b)) => Ok(Number(a * b)),
+        (BinaryOp::Div, Number(_), Number(0.0)) => {
+            Err(Diagnostic::error(codes::DIVISION_BY_ZERO, &tr("E0303.division", &[])))
+        }
+        (BinaryOp::Div, Number(a), Number(b)) => Ok(Number(a / b)),
+        (BinaryOp::Mod, Number(_), Number(0.0)) => {
+            Err(Diagnostic::error(codes::DIVISION_BY_ZERO, &tr("E0303.modulo", &[])))
+        }
+        (BinaryOp::Mod, Number(a), Number(b)) => Ok(Number(a % b)),
+        (BinaryOp::Pow, Number(a), Number(b)) => Ok(Number(a.powf(b))),
+
+        (BinaryOp::Gt, Number(a), Number(b)) => Ok(Bool(a > b)),
+        (BinaryOp::Gte, Number(a), Number(b)) => Ok(Bool(a >= b)),
+        (BinaryOp::Lt, Number(a), Number(b)) => Ok(Bool(a < b)),
+        (BinaryOp::Lte, Number(a), Number(b)) => Ok(Bool(a <= b)),
+        (BinaryOp::Gt, String(a), String(b)) => Ok(Bool(a > b)),
+        (BinaryOp::Gte, String(a), String(b)) => Ok(Bool(a >= b)),
+        (BinaryOp::Lt, String(a), String(b)) => Ok(Bool(a < b)),
+        (BinaryOp::Lte, String(a), String(b)) => Ok(Bool(a <= b)),
+
+        (op, l, r) => Err(Diagnostic::error(codes::TYPE_MISMATCH, &tr("E0302.binary", &[&op.as_str(), &l.type_name(), &r.type_name()]))),
+    }
+}
+
+/// Convierte un valor iterable en la secuencia de elementos que recorre `for ... in`.
+fn iterate(value: &Value) -> Result<Vec<Value>, Diagnostic> {
+    match value {
+        Value::List(items) => Ok(items.borrow().clone()),
+        Value::Map(entries) => Ok(entries.borrow().keys().cloned().map(Value::String).collect()),
+        Value::String(s) => Ok(s.chars().map(|c| Value::String(c.to_string())).collect()),
+        other => Err(Diagnostic::error(codes::NOT_ITERABLE, &tr(codes::NOT_ITERABLE, &[&other.type_name()]))),
+    }
+}
+
+/// Lee una propiedad de un valor: claves de mapas y `length` de listas y cadenas.
+fn get_property(object: &Value, name: &str) -> Result<Value, Diagnostic> {
+    match (object, name) {
+        (Value::Map(entries), _) => Ok(entries.borrow().get(name).cloned().unwrap_or(Value::None)),
+        (Value::List(items), "length") => Ok(Value::Number(items.borrow().len() as f64)),
+        (Value::String(s), "length") => Ok(Value::Number(s.chars().count() as f64)),
+        (other, _) => Err(Diagnostic::error(codes::INVALID_PROPERTY, &tr("E0307.get", &[&other.type_name(), &name]))),
+    }
+}
+
+fn set_property(object: Value, name: &str, value: Value) -> Result<Value, Diagnostic> {
+    match object {
+        Value::Map(entries) => {
+            entries.borrow_mut().insert(name.to_string(), value.clone());
+            Ok(value)
+        }
+        other => Err(Diagnostic::error(codes::INVALID_PROPERTY, &tr("E0307.set", &[&name, &other.type_name()]))),
+    }
+}
+
+/// Variable que no existe (o todavía no existe) al ejecutar.
+fn undefined(key: &str, name: &str) -> Diagnostic {
+    Diagnostic::error(codes::UNDEFINED_VARIABLE, &tr(key, &[&name]))
+}
+
+fn check_arity(name: &str, expected: usize, got: usize) -> Result<(), Diagnostic> {
+    if expected == got {
+        Ok(())
+    } else {
+        Err(Diagnostic::error(codes::ARITY_MISMATCH, &tr(codes::ARITY_MISMATCH, &[&name, &expected, &got])))
+    }
+}
+
+// ============================
+// 🧰 Funciones integradas
+// ============================
+
+/// Función nativa del ámbito global: su nombre, su firma y su implementación.
+pub struct Builtin {
+    /// Nombre con el que se la llama.
+    pub name: &'static str,
+    /// Tipos de los parámetros; `None` si acepta cualquier cantidad de argumentos.
+    pub params: Option<Vec<Ty>>,
+    /// Tipo del valor que devuelve.
+    pub ret: Ty,
+    /// Implementación.
+    pub func: NativeFn,
+}
+
+impl Builtin {
+    /// Cantidad de argumentos que espera; `None` si acepta cualquiera.
+    pub fn arity(&self) -> Option<usize> {
+        self.params.as_ref().map(Vec::len)
+    }
+}
+
+/// Tabla de funciones nativas disponibles en el ámbito global. Es la única
+/// lista: `resolve` declara estos nombres y `check` usa sus firmas.
+pub fn builtins() -> Vec<Builtin> {
+    let builtin = |name, params, ret, func| Builtin { name, params, ret, func };
+    let any_list = || Ty::List(Box::new(Ty::Any));
+    vec![
+        builtin("print", None, Ty::Null, builtin_print as NativeFn),
+        builtin("len", Some(vec![Ty::Any]), Ty::Int, builtin_len),
+        builtin("type", Some(vec![Ty::Any]), Ty::String, builtin_type),
+        builtin("string", Some(vec![Ty::Any]), Ty::String, builtin_string),
+        builtin("list", None, any_list(), builtin_list),
+        builtin("map", Some(vec![]), Ty::Map(Box::new(Ty::String), Box::new(Ty::Any)), builtin_map),
+        builtin("push", Some(vec![any_list(), Ty::Any]), Ty::Null, builtin_push),
+    ]
+}
+
+fn builtin_print(args: &[Value]) -> Result<Value, Diagnostic> {
+    let line: Vec<String> = args.iter().map(|v| v.to_string()).collect();
+    println!("{}", line.join(" "));
+    Ok(Value::None)
+}
+
+fn builtin_len(args: &[Value]) -> Result<Value, Diagnostic> {
+    match &args[0] {
+        Value::String(s) => Ok(Value::Number(s.chars().count() as f64)),
+        Value::List(items) => Ok(Value::Number(items.borrow().len() as f64)),
+        Value::Map(entries) => Ok(Value::Number(entries.borrow().len() as f64)),
+        other => Err(Diagnostic::error(codes::TYPE_MISMATCH, &tr("E0302.len", &[&other.type_name()]))),
+    }
+}
+
+fn builtin_type(args: &[Value]) -> Result<Value, Diagnostic> {
+    Ok(Value::String(args[0].type_name().to_string()))
+}
+
+fn builtin_string(args: &[Value]) -> Result<Value, Diagnostic> {
+    Ok(Value::String(args[0].to_string()))
+}
+
+fn builtin_list(args: &[Value]) -> Result<Value, Diagnostic> {
+    Ok(Value::List(Rc::new(RefCell::new(args.to_vec()))))
+}
+
+fn builtin_map(_args: &[Value]) -> Result<Value, Diagnostic> {
+    Ok(Value::Map(Rc::new(RefCell::new(BTreeMap::new()))))
+}
+
+fn builtin_push(args: &[Value]) -> Result<Value, Diagnostic> {
+    match &args[0] {
+        Value::List(items) => {
+            items.borrow_mut().push(args[1].clone());
+            Ok(Value::None)
+        }
+        other => Err(Diagnostic::error(codes::TYPE_MISMATCH, &tr("E0302.push", &[&other.type_name()]))),
+    }
+}
+
+// ============================
+// 🚪 Punto de entrada
+// ============================
+
+/// Ejecuta un programa (lista de instrucciones) en un intérprete nuevo
+/// y retorna su resultado. Si ya se resolvió (con `resolve` o
+/// `check_with_diagnostics`), `Interpreter::new(resolution).run(program)`
+/// evita resolverlo otra vez.
+pub fn evaluate(program: &[Statement]) -> Result<Value, Diagnostic> {
+    Interpreter::new(resolve(program)).run(program)
+}
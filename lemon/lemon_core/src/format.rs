@@ -0,0 +1,289 @@
+// ============================
+// format.rs — Formateador de código fuente
+// ============================
+
+use crate::diagnostic::{codes, Diagnostic};
+use crate::grammar::{Keyword, Symbol};
+use crate::i18n::{tr, Locale};
+use crate::lexer::{keyword_pragma, tokenize};
+use crate::span::Span;
+use crate::token::{Token, TokenType};
+
+/// Reescribe las palabras clave de `code` en el idioma `target` y deja el
+/// pragma `// lang: ...` acorde. Todo lo demás (espacios, comentarios,
+/// nombres) se conserva byte a byte, así que convertir un archivo en español
+/// a inglés y de vuelta a español devuelve el mismo texto.
+///
+/// Falla si el archivo tiene errores léxicos o si algún identificador pasaría
+/// a ser palabra reservada en `target` (por ejemplo, una variable `fin`).
+///
+/// ```
+/// # use lemon_core::format::convert_keywords;
+/// # use lemon_core::i18n::Locale;
+/// let es = "// lang: es\nsi x > 1\n  mostrar x\nfin\n";
+/// let en = convert_keywords(es, Locale::English).unwrap();
+/// assert_eq!(en, "// lang: en\nif x > 1\n  print x\nend\n");
+/// assert_eq!(convert_keywords(&en, Locale::Spanish).unwrap(), es);
+/// ```
+pub fn convert_keywords(code: &str, target: Locale) -> Result<String, Vec<Diagnostic>> {
+    let (tokens, diagnostics) = tokenize(code);
+    if diagnostics.iter().any(Diagnostic::is_error) {
+        return Err(diagnostics);
+    }
+
+    let mut edits: Vec<(Span, String)> = Vec::new();
+    let mut conflicts = Vec::new();
+    for token in &tokens {
+        match token.token_type {
+            TokenType::Keyword(kw) => {
+                let spelling = kw.spelling(target);
+                if token.value != spelling {
+                    edits.push((token.span, spelling.to_string()));
+                }
+            }
+            TokenType::Identifier if Keyword::parse_in(&token.value, target).is_some_and(|kw| !kw.is_contextual()) => {
+                conflicts.push(
+                    Diagnostic::error(codes::KEYWORD_CONFLICT, &tr(codes::KEYWORD_CONFLICT, &[&token.value, &target]))
+                        .with_span(token.span)
+                        .with_help(&tr("E0206.help", &[])),
+                );
+            }
+            _ => {}
+        }
+    }
+    if !conflicts.is_empty() {
+        return Err(conflicts);
+    }
+
+    // El pragma existente se actualiza con el mismo prefijo; si no hay, se
+    // agrega uno solo cuando hace falta (los archivos en inglés no lo necesitan).
+    match keyword_pragma(code) {
+        Some((_, span)) => {
+            let prefix = if code[span.start..span.end].contains("idioma:") { "idioma" } else { "lang" };
+            edits.push((span, format!("// {}: {}", prefix, target)));
+        }
+        None if target == Locale::Spanish => {
+            edits.push((Span::point(0), format!("// lang: {}\n", target)));
+        }
+        None => {}
+    }
+
+    Ok(apply_edits(code, edits))
+}
+
+/// Reescribe un archivo con bloques de estilo C (`{ }`, condiciones entre
+/// paréntesis, `fun`) en el estilo canónico con `end`:
+///
+/// - `{` desaparece y `}` pasa a ser `end`.
+/// - `} else if (c) {` pasa a `elif c`, y `} else {` a `else`.
+/// - `do { ... } while (c);` pasa a `do ... while c` + `end`.
+/// - Se quitan los paréntesis de las condiciones y de los encabezados de `for`,
+///   `fun` pasa a `fn` y se borra el `;` al final de cada línea.
+///
+/// Los comentarios, la sangría y el resto del texto no cambian, así que en un
+/// archivo que ya usa `end` solo se borran los `;` finales. Falla si el
+/// archivo tiene errores.
+///
+/// ```
+/// # use lemon_core::format::migrate_blocks;
+/// let c = "if (x > 0) {\n  print(x);\n} else {\n  print(0);\n}\n";
+/// assert_eq!(migrate_blocks(c).unwrap(), "if x > 0\n  print(x)\nelse\n  print(0)\nend\n");
+/// ```
+pub fn migrate_blocks(code: &str) -> Result<String, Vec<Diagnostic>> {
+    let (_, diagnostics) = crate::parse_with_diagnostics(code);
+    if diagnostics.iter().any(Diagnostic::is_error) {
+        return Err(diagnostics);
+    }
+
+    let (tokens, _) = tokenize(code);
+    let mut edits: Vec<(Span, String)> = Vec::new();
+    let mut open_blocks: Vec<(bool, usize)> = Vec::new(); // Por cada `{` abierta: si es de un `do`, y su línea
+    let mut interpolations = 0;                   // Las llaves de una interpolación no son bloques
+    let mut parens = 0;                           // Un `;` dentro de `for (...)` no se borra
+
+    for (i, token) in tokens.iter().enumerate() {
+        match token.token_type {
+            TokenType::InterpolationStart => interpolations += 1,
+            TokenType::InterpolationEnd => interpolations -= 1,
+            _ if interpolations > 0 => {}
+
+            TokenType::Symbol(Symbol::OpenParen) => parens += 1,
+            TokenType::Symbol(Symbol::CloseParen) => parens -= 1,
+
+            TokenType::Keyword(Keyword::Fn) if token.value == "fun" => {
+                edits.push((token.span, "fn".to_string()));
+            }
+            TokenType::Keyword(Keyword::If | Keyword::Elif | Keyword::While | Keyword::For) => {
+                // Solo los paréntesis que van directo antes de la `{` del cuerpo
+                if let Some(close) = matching_paren(&tokens, i + 1)
+                    && next_significant(&tokens, close + 1).token_type == TokenType::Symbol(Symbol::OpenBrace)
+                {
+                    unwrap_parens(&tokens, i, close, &mut edits);
+                }
+            }
+            TokenType::Keyword(Keyword::Fail) => {
+                // `fail (e)` pasa a `fail e`
+                if let Some(close) = matching_paren(&tokens, i + 1)
+                    && close == i + 3
+                {
+                    unwrap_parens(&tokens, i, close, &mut edits);
+                }
+            }
+
+            TokenType::Symbol(Symbol::OpenBrace) => {
+                let previous = tokens[..i].iter().rev().find(|t| t.token_type != TokenType::Newline);
+                let is_do_body = previous.is_some_and(|t| t.token_type == TokenType::Keyword(Keyword::Do));
+                open_blocks.push((is_do_body, token.line));
+                let from = previous.map_or(token.span.start, |t| t.span.end);
+                let from = if code[from..token.span.start].trim().is_empty() { from } else { token.span.start };
+                // `{x` en la misma línea necesita un espacio en lugar de la llave
+                let glued = code[token.span.end..].starts_with(|c: char| !c.is_whitespace());
+                edits.push((Span::new(from, token.span.end), if glued { " " } else { "" }.to_string()));
+            }
+            TokenType::Symbol(Symbol::CloseBrace) => {
+                let (is_do_body, open_line) = open_blocks.pop().unwrap_or((false, token.line));
+                let do_line = is_do_body.then_some(open_line);
+                migrate_close_brace(code, &tokens, i, do_line, &mut edits);
+            }
+
+            // `;` al final de la línea: sobra en el estilo canónico
+            TokenType::Symbol(Symbol::Semicolon) if parens == 0 => {
+                let next = &tokens[i + 1];
+                if next.token_type == TokenType::EOF || next.line > token.line {
+                    edits.push((token.span, String::new()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(apply_edits(code, edits))
+}
+
+/// Reemplaza la `}` en `tokens[i]`: `end`, o nada si la sigue `else`,
+/// `elif`, `fail` o el `while` de un `do`. `do_line` es la línea de la `{`
+/// cuando el bloque es el cuerpo de un `do`.
+fn migrate_close_brace(code: &str, tokens: &[Token], i: usize, do_line: Option<usize>, edits: &mut Vec<(Span, String)>) {
+    let brace = &tokens[i];
+    let next_index = (i + 1..tokens.len()).find(|&j| tokens[j].token_type != TokenType::Newline).unwrap_or(i);
+    let next = &tokens[next_index];
+
+    match next.token_type {
+        TokenType::Keyword(Keyword::Else | Keyword::Elif | Keyword::Fail) => {
+            remove_brace_before(code, brace, next, edits);
+            if next.token_type == TokenType::Keyword(Keyword::Else)
+                && tokens[next_index + 1].token_type == TokenType::Keyword(Keyword::If)
+            {
+                edits.push((next.span.to(tokens[next_index + 1].span), "elif".to_string()));
+            }
+        }
+        TokenType::Keyword(Keyword::While) if do_line.is_some() => {
+            // `x = 1 } while` pasa a `x = 1; while`: el `while` no termina la instrucción anterior
+            let previous = &tokens[i - 1];
+            if previous.line == brace.line && previous.token_type != TokenType::Symbol(Symbol::OpenBrace) {
+                edits.push((Span::new(previous.span.end, next.span.start), "; ".to_string()));
+            } else {
+                remove_brace_before(code, brace, next, edits);
+            }
+
+            // La condición llega hasta el fin de la línea o el `;`
+            let end = (next_index + 1..tokens.len())
+                .find(|&j| {
+                    matches!(
+                        tokens[j].token_type,
+                        TokenType::Newline | TokenType::EOF | TokenType::Symbol(Symbol::Semicolon | Symbol::CloseBrace)
+                    )
+                })
+                .unwrap_or(tokens.len() - 1);
+            if let Some(close) = matching_paren(tokens, next_index + 1)
+                && close == end - 1
+            {
+                unwrap_parens(tokens, next_index, close, edits);
+            }
+            // El `;` que sigue, si lo hay, lo borra el recorrido principal. Un
+            // `do { ... } while (c)` de una sola línea sigue en una línea.
+            let close = if do_line == Some(brace.line) {
+                " end".to_string()
+            } else {
+                format!("\n{}end", indentation(code, brace.span.start))
+            };
+            edits.push((Span::point(tokens[end - 1].span.end), close));
+        }
+        _ => {
+            let glued = code[..brace.span.start].ends_with(|c: char| !c.is_whitespace());
+            edits.push((brace.span, if glued { " end" } else { "end" }.to_string()));
+        }
+    }
+}
+
+/// Borra la `}` que precede a `next`. Si quedaba sola en su línea se borra
+/// la línea entera; si no, también los espacios hasta `next`.
+fn remove_brace_before(code: &str, brace: &Token, next: &Token, edits: &mut Vec<(Span, String)>) {
+    let line_start = code[..brace.span.start].rfind('\n').map_or(0, |i| i + 1);
+    let alone = code[line_start..brace.span.start].trim().is_empty()
+        && next.line > brace.line
+        && code[brace.span.end..].lines().next().is_some_and(|rest| rest.trim().is_empty());
+    if alone {
+        let line_end = code[brace.span.end..].find('\n').map_or(code.len(), |i| brace.span.end + i + 1);
+        edits.push((Span::new(line_start, line_end), String::new()));
+    } else if code[brace.span.end..next.span.start].trim().is_empty() {
+        // `a}else` necesita un espacio para no quedar `aelse`
+        let glued = code[..brace.span.start].ends_with(|c: char| !c.is_whitespace());
+        edits.push((Span::new(brace.span.start, next.span.start), if glued { " " } else { "" }.to_string()));
+    } else {
+        edits.push((brace.span, String::new()));
+    }
+}
+
+/// Quita los paréntesis `tokens[keyword + 1]` y `tokens[close]` que siguen a
+/// una palabra clave, dejando un espacio si iban pegados (`if(x)`).
+fn unwrap_parens(tokens: &[Token], keyword: usize, close: usize, edits: &mut Vec<(Span, String)>) {
+    let open = &tokens[keyword + 1];
+    let glued = tokens[keyword].span.end == open.span.start;
+    edits.push((open.span, if glued { " " } else { "" }.to_string()));
+    edits.push((tokens[close].span, String::new()));
+}
+
+/// Índice del `)` que cierra el `(` en `tokens[open]`, si ese token es un `(`.
+fn matching_paren(tokens: &[Token], open: usize) -> Option<usize> {
+    if tokens.get(open)?.token_type != TokenType::Symbol(Symbol::OpenParen) {
+        return None;
+    }
+    let mut depth = 0;
+    for (j, token) in tokens.iter().enumerate().skip(open) {
+        match token.token_type {
+            TokenType::Symbol(Symbol::OpenParen) => depth += 1,
+            TokenType::Symbol(Symbol::CloseParen) if depth == 1 => return Some(j),
+            TokenType::Symbol(Symbol::CloseParen) => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Primer token desde `from` que no es un salto de línea.
+fn next_significant(tokens: &[Token], from: usize) -> &Token {
+    tokens[from..].iter().find(|t| t.token_type != TokenType::Newline).unwrap_or(&tokens[tokens.len() - 1])
+}
+
+/// Espacios y tabulaciones al inicio de la línea que contiene `offset`.
+fn indentation(code: &str, offset: usize) -> &str {
+    let line_start = code[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let line = &code[line_start..];
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+/// Aplica ediciones `(rango, texto nuevo)` sobre `code`. Las ediciones no se
+/// superponen; las que empiezan en el mismo byte se aplican en orden.
+fn apply_edits(code: &str, mut edits: Vec<(Span, String)>) -> String {
+    edits.sort_by_key(|(span, _)| span.start);
+    let mut out = String::with_capacity(code.len());
+    let mut last = 0;
+    for (span, text) in edits {
+        out.push_str(&code[last..span.start]);
+        out.push_str(&text);
+        last = span.end;
+    }
+    out.push_str(&code[last..]);
+    out
+}
@@ -13,6 +13,9 @@ pub enum Comment {
 
     /// Fin de un comentario en bloque (termina con `*/`).
     BlockCommentClose,
+
+    /// Comentario en bloque completo, de `/*` a `*/` (como trivia).
+    BlockComment,
 }
 
 impl Comment {
@@ -22,11 +25,12 @@ impl Comment {
     ///
     /// # Ejemplo
     /// ```
-    /// assert_eq!(Comment::from_str("//"), Some(Comment::LineComment));
+    /// # use lemon_core::grammar::Comment;
+    /// assert_eq!(Comment::parse("//"), Some(Comment::LineComment));
     /// ```
     ///
     /// Retorna `None` si la cadena no representa un delimitador de comentario reconocido.
-    pub fn from_str(s: &str) -> Option<Comment> {
+    pub fn parse(s: &str) -> Option<Comment> {
         match s {
             "//" => Some(Comment::LineComment),
             "/*" => Some(Comment::BlockCommentOpen),
@@ -19,6 +19,16 @@ pub enum Comparator {
     LessEqual,
 }
 
+/// Texto fuente de cada comparador.
+pub const COMPARATORS: &[(&str, Comparator)] = &[
+    ("==", Comparator::Equal),
+    ("!=", Comparator::NotEqual),
+    (">", Comparator::Greater),
+    ("<", Comparator::Less),
+    (">=", Comparator::GreaterEqual),
+    ("<=", Comparator::LessEqual),
+];
+
 impl Comparator {
     /// Intenta construir un `Comparator` a partir de una cadena.
     ///
@@ -27,7 +37,8 @@ impl Comparator {
     ///
     /// # Ejemplo
     /// ```
-    /// assert_eq!(Comparator::from_str(">="), Some(Comparator::GreaterEqual));
+    /// # use lemon_core::grammar::Comparator;
+    /// assert_eq!(Comparator::parse(">="), Some(Comparator::GreaterEqual));
     /// ```
     ///
     /// # Parámetros
@@ -36,15 +47,7 @@ impl Comparator {
     /// # Retorna
     /// - `Some(Comparator)` si la cadena coincide con un comparador conocido.
     /// - `None` si no coincide con ningún operador válido.
-    pub fn from_str(s: &str) -> Option<Comparator> {
-        match s {
-            "==" => Some(Comparator::Equal),
-            "!=" => Some(Comparator::NotEqual),
-            ">"  => Some(Comparator::Greater),
-            "<"  => Some(Comparator::Less),
-            ">=" => Some(Comparator::GreaterEqual),
-            "<=" => Some(Comparator::LessEqual),
-            _    => None, // No coincide con ningún comparador válido
-        }
+    pub fn parse(s: &str) -> Option<Comparator> {
+        COMPARATORS.iter().find(|(text, _)| *text == s).map(|&(_, cmp)| cmp)
     }
 }
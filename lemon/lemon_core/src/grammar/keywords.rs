@@ -1,3 +1,4 @@
+use crate::i18n::Locale;
 use serde::{Serialize, Deserialize};
 
 /// Enum que representa todas las palabras clave (`keywords`) reservadas del lenguaje Yuka.
@@ -5,6 +6,9 @@ use serde::{Serialize, Deserialize};
 /// Estas palabras no pueden ser utilizadas como identificadores, ya que tienen un significado
 /// especial para el analizador sintáctico y el compilador. Se utilizan para declarar control
 /// de flujo, funciones, UI, comportamiento, entre otros.
+///
+/// La excepción son las palabras contextuales de la interfaz gráfica (ver `is_contextual`),
+/// que siguen siendo nombres válidos fuera de su contexto.
 #[derive(Debug, Clone, PartialEq, Copy, Serialize, Deserialize)]
 pub enum Keyword {
     // Control de flujo
@@ -78,14 +82,15 @@ pub enum Keyword {
 impl Keyword {
     /// Convierte una cadena (`&str`) a un valor `Keyword` si coincide con alguna palabra clave válida.
     ///
-    /// La conversión es **insensible a mayúsculas** (`case-insensitive`), por lo que `"IF"` y `"if"`
-    /// se tratan como equivalentes.
+    /// La conversión es **sensible a mayúsculas**: `"if"` es una palabra clave, pero `"IF"` o
+    /// `"If"` son identificadores comunes.
     ///
     /// # Ejemplo
     /// ```
-    /// assert_eq!(Keyword::from_str("while"), Some(Keyword::While));
-    /// assert_eq!(Keyword::from_str("WHILE"), Some(Keyword::While));
-    /// assert_eq!(Keyword::from_str("xyz"), None);
+    /// # use lemon_core::grammar::Keyword;
+    /// assert_eq!(Keyword::parse("while"), Some(Keyword::While));
+    /// assert_eq!(Keyword::parse("WHILE"), None);
+    /// assert_eq!(Keyword::parse("xyz"), None);
     /// ```
     ///
     /// # Parámetros
@@ -93,8 +98,8 @@ impl Keyword {
     ///
     /// # Retorna
     /// `Some(Keyword)` si coincide con una palabra clave válida, o `None` en caso contrario.
-    pub fn from_str(s: &str) -> Option<Keyword> {
-        match s.to_lowercase().as_str() {
+    pub fn parse(s: &str) -> Option<Keyword> {
+        match s {
             // Control de flujo
             "if" => Some(Keyword::If),
             "elif" => Some(Keyword::Elif),
@@ -118,7 +123,7 @@ impl Keyword {
 
             // Declaraciones
             "let" => Some(Keyword::Let),
-            "fn" => Some(Keyword::Fn),
+            "fn" | "fun" => Some(Keyword::Fn), // `fun` es la forma del estilo con llaves
             "from" => Some(Keyword::From),
             "as" => Some(Keyword::As),
             "none" => Some(Keyword::None),
@@ -144,7 +149,7 @@ impl Keyword {
             "old" => Some(Keyword::Old),
 
             // Entrada/salida y herramientas
-            "print" => Some(Keyword::Print),
+            "print" | "show" => Some(Keyword::Print),
             "ask" => Some(Keyword::Ask),
             "read" => Some(Keyword::Read),
             "save" => Some(Keyword::Save),
@@ -166,4 +171,175 @@ impl Keyword {
             _ => None,
         }
     }
+
+    /// Igual que `parse`, pero con la tabla de palabras clave del idioma
+    /// del archivo. En español se aceptan los alias (`si`, `mientras`, `fin`, ...)
+    /// además de las palabras en inglés, que siguen siendo válidas.
+    ///
+    /// ```
+    /// # use lemon_core::grammar::Keyword;
+    /// # use lemon_core::i18n::Locale;
+    /// assert_eq!(Keyword::parse_in("mientras", Locale::Spanish), Some(Keyword::While));
+    /// assert_eq!(Keyword::parse_in("while", Locale::Spanish), Some(Keyword::While));
+    /// assert_eq!(Keyword::parse_in("mientras", Locale::English), None);
+    /// ```
+    pub fn parse_in(s: &str, locale: Locale) -> Option<Keyword> {
+        if locale == Locale::Spanish
+            && let Some(&(_, kw)) = SPANISH_KEYWORDS.iter().find(|(alias, _)| *alias == s)
+        {
+            return Some(kw);
+        }
+        Self::parse(s)
+    }
+
+    /// Indica si la palabra clave es contextual: los elementos de la interfaz
+    /// gráfica (`btn`, `txt`, `box`, `window`, ...) solo son palabras clave al
+    /// inicio de una instrucción o dentro de una sección `ui`. El lexer las
+    /// entrega como identificadores y el parser decide según el contexto, así
+    /// que `let window = 1` o `print(box)` son válidos.
+    ///
+    /// ```
+    /// # use lemon_core::grammar::Keyword;
+    /// assert!(Keyword::Window.is_contextual());
+    /// assert!(!Keyword::While.is_contextual());
+    /// ```
+    pub fn is_contextual(self) -> bool {
+        matches!(
+            self,
+            Keyword::Btn
+                | Keyword::Txt
+                | Keyword::Img
+                | Keyword::Vid
+                | Keyword::Canvas
+                | Keyword::Div
+                | Keyword::Click
+                | Keyword::Window
+                | Keyword::Tick
+                | Keyword::Wait
+                | Keyword::Box
+        )
+    }
+
+    /// Forma canónica (en inglés) de la palabra clave.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Keyword::If => "if",
+            Keyword::Elif => "elif",
+            Keyword::Else => "else",
+            Keyword::Do => "do",
+            Keyword::While => "while",
+            Keyword::For => "for",
+            Keyword::When => "when",
+            Keyword::In => "in",
+            Keyword::Try => "try",
+            Keyword::Fail => "fail",
+            Keyword::End => "end",
+            Keyword::Break => "break",
+            Keyword::Continue => "continue",
+            Keyword::Return => "return",
+            Keyword::Exit => "exit",
+            Keyword::Let => "let",
+            Keyword::Fn => "fn",
+            Keyword::From => "from",
+            Keyword::As => "as",
+            Keyword::None => "none",
+            Keyword::Back => "back",
+            Keyword::Send => "send",
+            Keyword::Del => "del",
+            Keyword::Run => "run",
+            Keyword::Log => "log",
+            Keyword::Box => "box",
+            Keyword::Wrap => "wrap",
+            Keyword::True => "true",
+            Keyword::False => "false",
+            Keyword::Sure => "sure",
+            Keyword::Stay => "stay",
+            Keyword::Fast => "fast",
+            Keyword::Flat => "flat",
+            Keyword::Old => "old",
+            Keyword::Print => "print",
+            Keyword::Ask => "ask",
+            Keyword::Read => "read",
+            Keyword::Save => "save",
+            Keyword::Ui => "ui",
+            Keyword::Btn => "btn",
+            Keyword::Txt => "txt",
+            Keyword::Img => "img",
+            Keyword::Vid => "vid",
+            Keyword::Canvas => "canvas",
+            Keyword::Div => "div",
+            Keyword::Click => "click",
+            Keyword::Window => "window",
+            Keyword::Tick => "tick",
+            Keyword::Wait => "wait",
+        }
+    }
+
+    /// Cómo se escribe la palabra clave en un idioma. Las que no tienen
+    /// alias en español conservan su forma en inglés.
+    ///
+    /// ```
+    /// # use lemon_core::grammar::Keyword;
+    /// # use lemon_core::i18n::Locale;
+    /// assert_eq!(Keyword::End.spelling(Locale::Spanish), "fin");
+    /// assert_eq!(Keyword::Fn.spelling(Locale::Spanish), "fn");
+    /// assert_eq!(Keyword::End.spelling(Locale::English), "end");
+    /// ```
+    pub fn spelling(self, locale: Locale) -> &'static str {
+        if locale == Locale::Spanish
+            && let Some(&(alias, _)) = SPANISH_KEYWORDS.iter().find(|(_, kw)| *kw == self)
+        {
+            return alias;
+        }
+        self.as_str()
+    }
 }
+
+/// Alias en español de las palabras clave. Cada variante aparece una sola
+/// vez, así la tabla sirve en ambos sentidos (leer y escribir código).
+///
+/// Solo se activan en archivos en español (ver `lexer::keyword_pragma`), de
+/// modo que en un archivo en inglés `fin` o `para` siguen siendo identificadores.
+pub const SPANISH_KEYWORDS: &[(&str, Keyword)] = &[
+    // Control de flujo
+    ("si", Keyword::If),
+    ("sinosi", Keyword::Elif),
+    ("sino", Keyword::Else),
+    ("hacer", Keyword::Do),
+    ("mientras", Keyword::While),
+    ("para", Keyword::For),
+    ("cuando", Keyword::When),
+    ("en", Keyword::In),
+
+    // Manejo de errores
+    ("intentar", Keyword::Try),
+    ("fallo", Keyword::Fail),
+
+    // Estructura y control de ejecución
+    ("fin", Keyword::End),
+    ("romper", Keyword::Break),
+    ("continuar", Keyword::Continue),
+    ("devolver", Keyword::Return),
+    ("salir", Keyword::Exit),
+
+    // Declaraciones (`fn` se escribe igual en ambos idiomas)
+    ("sea", Keyword::Let),
+    ("desde", Keyword::From),
+    ("como", Keyword::As),
+    ("nada", Keyword::None),
+
+    // Funcionalidad adicional
+    ("enviar", Keyword::Send),
+    ("borrar", Keyword::Del),
+    ("ejecutar", Keyword::Run),
+
+    // Booleanos
+    ("verdadero", Keyword::True),
+    ("falso", Keyword::False),
+
+    // Entrada/salida
+    ("mostrar", Keyword::Print),
+    ("preguntar", Keyword::Ask),
+    ("leer", Keyword::Read),
+    ("guardar", Keyword::Save),
+];
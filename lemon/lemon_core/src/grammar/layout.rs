@@ -0,0 +1,34 @@
+use crate::grammar::{Keyword, Symbol};
+use crate::token::TokenType;
+
+// Reglas de la gramática que comparten el lexer (para seguir los bloques
+// abiertos y las líneas que continúan) y el parser.
+
+/// Palabras clave que cierran o dividen un bloque terminado en `end`.
+pub(crate) const BLOCK_BOUNDARIES: [Keyword; 4] = [Keyword::End, Keyword::Elif, Keyword::Else, Keyword::Fail];
+
+/// Elementos de la interfaz que abren un bloque: contenedores y eventos.
+pub(crate) const UI_CONTAINERS: [Keyword; 5] = [Keyword::Wrap, Keyword::Div, Keyword::Window, Keyword::Tick, Keyword::Click];
+
+/// Indica si un token, después de un nombre, sigue una expresión: `window = 1`,
+/// `box.valor`, `click(x)`. Así se distingue un nombre de una palabra contextual.
+pub(crate) fn continues_expression(token_type: TokenType) -> bool {
+    matches!(
+        token_type,
+        TokenType::Symbol(
+            Symbol::Define
+                | Symbol::AddAssign
+                | Symbol::SubtractAssign
+                | Symbol::MultiplyAssign
+                | Symbol::DivideAssign
+                | Symbol::OpenParen
+                | Symbol::Dot
+                | Symbol::SafeDot
+                | Symbol::OpenBraket
+                | Symbol::Question
+                | Symbol::Coalesce
+        ) | TokenType::Operator(_)
+            | TokenType::Comparator(_)
+            | TokenType::Logical(_)
+    )
+}
@@ -17,6 +17,21 @@ pub enum Logical {
     Not,
 }
 
+/// Formas verbales de los operadores lógicos. Son palabras: el lexer las
+/// reconoce junto con las palabras clave (ver `Logical::from_word`).
+pub const LOGICAL_WORDS: &[(&str, Logical)] = &[
+    ("and", Logical::And),
+    ("or", Logical::Or),
+    ("not", Logical::Not),
+];
+
+/// Formas simbólicas de los operadores lógicos.
+pub const LOGICAL_SYMBOLS: &[(&str, Logical)] = &[
+    ("&&", Logical::And),
+    ("||", Logical::Or),
+    ("!", Logical::Not),
+];
+
 impl Logical {
     /// Intenta convertir una cadena en un operador lógico válido.
     ///
@@ -24,8 +39,9 @@ impl Logical {
     ///
     /// # Ejemplo
     /// ```
-    /// assert_eq!(Logical::from_str("and"), Some(Logical::And));
-    /// assert_eq!(Logical::from_str("||"), Some(Logical::Or));
+    /// # use lemon_core::grammar::Logical;
+    /// assert_eq!(Logical::parse("and"), Some(Logical::And));
+    /// assert_eq!(Logical::parse("||"), Some(Logical::Or));
     /// ```
     ///
     /// # Parámetros
@@ -34,16 +50,20 @@ impl Logical {
     /// # Retorna
     /// - `Some(Logical)` si la cadena representa un operador válido.
     /// - `None` si no se reconoce.
-    pub fn from_str(s: &str) -> Option<Logical> {
-        match s.to_lowercase().as_str() {
-            "and" => Some(Logical::And),
-            "&&"  => Some(Logical::And),
-            "or"  => Some(Logical::Or),
-            "||"  => Some(Logical::Or),
-            "not" => Some(Logical::Not),
-            "!"   => Some(Logical::Not),
-            _     => None,
-        }
+    pub fn parse(s: &str) -> Option<Logical> {
+        Self::from_word(s).or_else(|| LOGICAL_SYMBOLS.iter().find(|(text, _)| *text == s).map(|&(_, log)| log))
+    }
+
+    /// Operador lógico escrito como palabra. A diferencia de las palabras
+    /// clave, no distingue mayúsculas (`AND`, `Or`).
+    ///
+    /// ```
+    /// # use lemon_core::grammar::Logical;
+    /// assert_eq!(Logical::from_word("Not"), Some(Logical::Not));
+    /// assert_eq!(Logical::from_word("!"), None);
+    /// ```
+    pub fn from_word(word: &str) -> Option<Logical> {
+        LOGICAL_WORDS.iter().find(|(text, _)| text.eq_ignore_ascii_case(word)).map(|&(_, log)| log)
     }
 }
 
@@ -5,6 +5,7 @@ pub mod comparators;
 pub mod logicals;
 pub mod types;
 pub mod comments;
+mod layout;
 
 pub use keywords::*;
 pub use symbols::*;
@@ -12,4 +13,5 @@ pub use operators::*;
 pub use comparators::*;
 pub use logicals::*;
 pub use types::*;
-pub use comments::*;
\ No newline at end of file
+pub use comments::*;
+pub(crate) use layout::{continues_expression, BLOCK_BOUNDARIES, UI_CONTAINERS};
\ No newline at end of file
@@ -16,10 +16,21 @@ pub enum Operator {
     Divide,
     /// Módulo o residuo: `%`
     Modulus,
-    /// Potenciación: `^`
+    /// Potenciación: `^` o `**`
     Power,
 }
 
+/// Texto fuente de cada operador. `**` es un alias de `^`.
+pub const OPERATORS: &[(&str, Operator)] = &[
+    ("+", Operator::Add),
+    ("-", Operator::Subtract),
+    ("*", Operator::Multiply),
+    ("/", Operator::Divide),
+    ("%", Operator::Modulus),
+    ("^", Operator::Power),
+    ("**", Operator::Power),
+];
+
 impl Operator {
     /// Intenta convertir una cadena en un operador aritmético válido.
     ///
@@ -28,7 +39,9 @@ impl Operator {
     ///
     /// # Ejemplo
     /// ```
-    /// assert_eq!(Operator::from_str("*"), Some(Operator::Multiply));
+    /// # use lemon_core::grammar::Operator;
+    /// assert_eq!(Operator::parse("*"), Some(Operator::Multiply));
+    /// assert_eq!(Operator::parse("**"), Some(Operator::Power));
     /// ```
     ///
     /// # Parámetros
@@ -37,16 +50,8 @@ impl Operator {
     /// # Retorna
     /// - `Some(Operator)` si la cadena representa un operador reconocido.
     /// - `None` si no coincide con ningún operador válido.
-    pub fn from_str(s: &str) -> Option<Operator> {
-        match s {
-            "+" => Some(Operator::Add),
-            "-" => Some(Operator::Subtract),
-            "*" => Some(Operator::Multiply),
-            "/" => Some(Operator::Divide),
-            "%" => Some(Operator::Modulus),
-            "^" => Some(Operator::Power),
-            _   => None,
-        }
+    pub fn parse(s: &str) -> Option<Operator> {
+        OPERATORS.iter().find(|(text, _)| *text == s).map(|&(_, op)| op)
     }
 }
 
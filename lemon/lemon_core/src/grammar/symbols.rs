@@ -10,6 +10,15 @@ pub enum Symbol {
     /// Símbolo de definición o asignación: `=`
     Define,
 
+    /// Asignación con suma: `+=`
+    AddAssign,
+    /// Asignación con resta: `-=`
+    SubtractAssign,
+    /// Asignación con multiplicación: `*=`
+    MultiplyAssign,
+    /// Asignación con división: `/=`
+    DivideAssign,
+
     /// Paréntesis izquierdo: `(`
     OpenParen,
     /// Paréntesis derecho: `)`
@@ -34,68 +43,89 @@ pub enum Symbol {
     /// Punto: `.`
     Dot,
 
+    /// Acceso opcional a una propiedad: `?.`
+    SafeDot,
+
+    /// Rango sin el extremo final: `..`
+    Range,
+
+    /// Rango con el extremo final: `..=`
+    RangeInclusive,
+
     /// Signo de interrogación: `?`
     Question,
 
+    /// Valor alternativo si el de la izquierda es `none`: `??`
+    Coalesce,
+
     /// Dos puntos: `:`
     Colon,
+
+    /// Flecha: `->`
+    Arrow,
+
+    /// Flecha doble: `=>`
+    FatArrow,
 }
 
+/// Texto fuente de cada símbolo.
+pub const SYMBOLS: &[(&str, Symbol)] = &[
+    ("=", Symbol::Define),
+    ("+=", Symbol::AddAssign),
+    ("-=", Symbol::SubtractAssign),
+    ("*=", Symbol::MultiplyAssign),
+    ("/=", Symbol::DivideAssign),
+    ("(", Symbol::OpenParen),
+    (")", Symbol::CloseParen),
+    ("{", Symbol::OpenBrace),
+    ("}", Symbol::CloseBrace),
+    ("[", Symbol::OpenBraket),
+    ("]", Symbol::CloseBraket),
+    (";", Symbol::Semicolon),
+    (",", Symbol::Comma),
+    (".", Symbol::Dot),
+    ("?.", Symbol::SafeDot),
+    ("..", Symbol::Range),
+    ("..=", Symbol::RangeInclusive),
+    ("?", Symbol::Question),
+    ("??", Symbol::Coalesce),
+    (":", Symbol::Colon),
+    ("->", Symbol::Arrow),
+    ("=>", Symbol::FatArrow),
+];
+
 impl Symbol {
-    /// Intenta convertir un carácter (`char`) en un símbolo reconocido por Yuka.
+    /// Intenta convertir un texto en un símbolo reconocido por Yuka.
     ///
     /// Esta función se usa típicamente durante el análisis léxico.
     ///
     /// # Ejemplo
     /// ```
-    /// assert_eq!(Symbol::from_char('('), Some(Symbol::OpenParen));
-    /// assert_eq!(Symbol::from_char('x'), None);
+    /// # use lemon_core::grammar::Symbol;
+    /// assert_eq!(Symbol::parse("("), Some(Symbol::OpenParen));
+    /// assert_eq!(Symbol::parse("..="), Some(Symbol::RangeInclusive));
+    /// assert_eq!(Symbol::parse("x"), None);
     /// ```
     ///
     /// # Parámetros
-    /// - `c`: Carácter que se desea interpretar.
+    /// - `s`: Texto que se desea interpretar.
     ///
     /// # Retorna
-    /// - `Some(Symbol)` si el carácter corresponde a un símbolo conocido.
+    /// - `Some(Symbol)` si el texto corresponde a un símbolo conocido.
     /// - `None` si no coincide con ningún símbolo válido.
-    pub fn from_char(c: char) -> Option<Self> {
-        match c {
-            '=' => Some(Self::Define),
-            '(' => Some(Self::OpenParen),
-            ')' => Some(Self::CloseParen),
-            '{' => Some(Self::OpenBrace),
-            '}' => Some(Self::CloseBrace),
-            '[' => Some(Self::OpenBraket),
-            ']' => Some(Self::CloseBraket),
-            ';' => Some(Self::Semicolon),
-            ',' => Some(Self::Comma),
-            '.' => Some(Self::Dot),
-            '?' => Some(Self::Question),
-            ':' => Some(Self::Colon),
-            _ => None, // Carácter no reconocido como símbolo
-        }
+    pub fn parse(s: &str) -> Option<Self> {
+        SYMBOLS.iter().find(|(text, _)| *text == s).map(|&(_, symbol)| symbol)
     }
 
-    /// Convierte el símbolo interno en su representación como carácter (`char`).
+    /// Texto fuente del símbolo.
     ///
     /// # Ejemplo
     /// ```
-    /// assert_eq!(Symbol::OpenBrace.to_char(), '{');
+    /// # use lemon_core::grammar::Symbol;
+    /// assert_eq!(Symbol::OpenBrace.as_str(), "{");
+    /// assert_eq!(Symbol::Coalesce.as_str(), "??");
     /// ```
-    pub fn to_char(&self) -> char {
-        match self {
-            Self::Define        => '=',
-            Self::OpenParen     => '(',
-            Self::CloseParen    => ')',
-            Self::OpenBrace     => '{',
-            Self::CloseBrace    => '}',
-            Self::OpenBraket    => '[',
-            Self::CloseBraket   => ']',
-            Self::Semicolon     => ';',
-            Self::Comma         => ',',
-            Self::Dot           => '.',
-            Self::Question      => '?',
-            Self::Colon         => ':',
-        }
+    pub fn as_str(&self) -> &'static str {
+        SYMBOLS.iter().find(|(_, symbol)| symbol == self).map_or("", |&(text, _)| text)
     }
 }
@@ -38,9 +38,10 @@ impl Type {
     ///
     /// # Ejemplo
     /// ```
-    /// assert_eq!(Type::from_str("Float"), Some(Type::Float));
-    /// assert_eq!(Type::from_str("map"), Some(Type::Map));
-    /// assert_eq!(Type::from_str("char"), None); // Tipo no reconocido
+    /// # use lemon_core::grammar::Type;
+    /// assert_eq!(Type::parse("Float"), Some(Type::Float));
+    /// assert_eq!(Type::parse("map"), Some(Type::Map));
+    /// assert_eq!(Type::parse("char"), None); // Tipo no reconocido
     /// ```
     ///
     /// # Parámetros
@@ -48,17 +49,37 @@ impl Type {
     ///
     /// # Retorna
     /// `Some(Type)` si se reconoce como tipo válido, `None` en caso contrario.
-    pub fn from_str(s: &str) -> Option<Type> {
-        match s.to_lowercase().as_str() {
-            "int"    => Some(Type::Int),
-            "float"  => Some(Type::Float),
-            "string" => Some(Type::String),
-            "bool"   => Some(Type::Bool),
-            "null"   => Some(Type::Null),
-            "list"   => Some(Type::List),
-            "matrix" => Some(Type::Matrix),
-            "map"    => Some(Type::Map),
-            _        => None, // Tipo no reconocido
+    pub fn parse(s: &str) -> Option<Type> {
+        // `eq_ignore_ascii_case` compara sin crear una copia en minúsculas
+        const NAMES: [(&str, Type); 8] = [
+            ("int", Type::Int),
+            ("float", Type::Float),
+            ("string", Type::String),
+            ("bool", Type::Bool),
+            ("null", Type::Null),
+            ("list", Type::List),
+            ("matrix", Type::Matrix),
+            ("map", Type::Map),
+        ];
+        NAMES.iter().find(|(name, _)| name.eq_ignore_ascii_case(s)).map(|&(_, ty)| ty)
+    }
+
+    /// Nombre del tipo como se escribe en el código.
+    ///
+    /// ```
+    /// # use lemon_core::grammar::Type;
+    /// assert_eq!(Type::Matrix.as_str(), "matrix");
+    /// ```
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Type::Int => "int",
+            Type::Float => "float",
+            Type::String => "string",
+            Type::Bool => "bool",
+            Type::Null => "null",
+            Type::List => "list",
+            Type::Matrix => "matrix",
+            Type::Map => "map",
         }
     }
 }
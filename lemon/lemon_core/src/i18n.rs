@@ -0,0 +1,242 @@
+// ============================
+// i18n.rs — Catálogo de mensajes (español / inglés)
+// ============================
+
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+// ============================
+// 🌐 Idioma
+// ============================
+
+/// Idioma de los mensajes. El español es el idioma por defecto del proyecto.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    Spanish,
+    English,
+}
+
+/// Variable de entorno que elige el idioma (`es`, `en`, `es_AR.UTF-8`, ...).
+pub const LOCALE_ENV_VAR: &str = "YUKA_LANG";
+
+impl Locale {
+    /// Interpreta una etiqueta de idioma. Solo se mira el idioma base, así
+    /// que `es`, `es-MX` y `es_ES.UTF-8` son todos español.
+    ///
+    /// ```
+    /// # use lemon_core::i18n::Locale;
+    /// assert_eq!(Locale::from_tag("en_US.UTF-8"), Some(Locale::English));
+    /// assert_eq!(Locale::from_tag("español"), Some(Locale::Spanish));
+    /// assert_eq!(Locale::from_tag("fr"), None);
+    /// ```
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let base = tag.split(['_', '-', '.']).next()?.trim().to_lowercase();
+        match base.as_str() {
+            "es" | "spa" | "español" | "espanol" | "spanish" => Some(Locale::Spanish),
+            "en" | "eng" | "english" | "inglés" | "ingles" => Some(Locale::English),
+            _ => None,
+        }
+    }
+
+    /// Idioma indicado en `YUKA_LANG`, si la variable existe y es válida.
+    pub fn from_env() -> Option<Self> {
+        std::env::var(LOCALE_ENV_VAR).ok().and_then(|tag| Self::from_tag(&tag))
+    }
+
+    /// Etiqueta corta del idioma (`es` o `en`).
+    pub fn tag(self) -> &'static str {
+        match self {
+            Locale::Spanish => "es",
+            Locale::English => "en",
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.tag())
+    }
+}
+
+/// Idioma activo, compartido por todo el proceso (0 = español, 1 = inglés).
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+/// Cambia el idioma de los mensajes que se generen a partir de ahora.
+pub fn set_locale(locale: Locale) {
+    CURRENT.store(locale as u8, Ordering::Relaxed);
+}
+
+/// Idioma activo.
+pub fn locale() -> Locale {
+    match CURRENT.load(Ordering::Relaxed) {
+        1 => Locale::English,
+        _ => Locale::Spanish,
+    }
+}
+
+// ============================
+// 📚 Catálogo
+// ============================
+
+/// Plantilla de una clave en un idioma, si existe.
+pub fn template(key: &str, locale: Locale) -> Option<&'static str> {
+    CATALOG.iter().find(|(k, _, _)| *k == key).map(|(_, es, en)| match locale {
+        Locale::Spanish => *es,
+        Locale::English => *en,
+    })
+}
+
+/// Mensaje de `key` en el idioma activo, con `{0}`, `{1}`, ... reemplazados
+/// por `args`. Una clave que no está en el catálogo se devuelve tal cual.
+///
+/// ```
+/// # use lemon_core::i18n::{tr, set_locale, Locale};
+/// set_locale(Locale::English);
+/// assert_eq!(tr("E0301", &[&"x"]), "undefined variable: 'x'");
+/// set_locale(Locale::Spanish);
+/// assert_eq!(tr("E0301", &[&"x"]), "variable no definida: 'x'");
+/// ```
+pub fn tr(key: &str, args: &[&dyn fmt::Display]) -> String {
+    let Some(text) = template(key, locale()) else {
+        return key.to_string();
+    };
+    let mut text = text.to_string();
+    for (i, arg) in args.iter().enumerate() {
+        text = text.replace(&format!("{{{}}}", i), &arg.to_string());
+    }
+    text
+}
+
+/// Claves y plantillas `(clave, español, inglés)`.
+///
+/// La clave de un diagnóstico es su código estable (ver `diagnostic::codes`);
+/// si un código tiene varios mensajes, se agrega un sufijo (`E0201.block_end`).
+const CATALOG: &[(&str, &str, &str)] = &[
+    // Gravedad y secciones del diagnóstico
+    ("severity.error", "error", "error"),
+    ("severity.warning", "advertencia", "warning"),
+    ("severity.note", "nota", "note"),
+    ("render.note", "nota", "note"),
+    ("render.help", "ayuda", "help"),
+
+    // Léxicos
+    ("E0101", "comentario multilínea sin cerrar", "unclosed multiline comment"),
+    ("E0102", "cadena sin comilla de cierre", "unclosed string literal"),
+    ("E0102.block", "bloque de texto sin '\"\"\"' de cierre", "text block has no closing '\"\"\"'"),
+    ("E0103", "secuencia de escape inválida", "invalid escape sequence"),
+    ("E0103.hex", "escape '\\x' inválido: se esperaban dos dígitos hexadecimales entre 00 y 7F", "invalid '\\x' escape: expected two hex digits between 00 and 7F"),
+    ("E0103.unicode", "escape unicode inválido: se esperaba '\\u{...}' con 1 a 6 dígitos hexadecimales", "invalid unicode escape: expected '\\u{...}' with 1 to 6 hex digits"),
+    ("E0103.scalar", "'{0}' no es un carácter unicode válido", "'{0}' is not a valid unicode character"),
+    ("E0103.help", "escapes válidos: \\n \\t \\r \\0 \\\\ \\\" \\xNN \\u{...}; usa r\"...\" para una cadena cruda", "valid escapes: \\n \\t \\r \\0 \\\\ \\\" \\xNN \\u{...}; use r\"...\" for a raw string"),
+    ("E0104", "carácter de control no permitido", "illegal control character"),
+    ("E0105", "carácter no permitido", "illegal character in input"),
+    ("E0106", "token no reconocido", "unrecognized token"),
+    ("E0107", "número mal formado: '{0}'", "malformed number: '{0}'"),
+    ("E0107.identifier", "los nombres no pueden empezar con un dígito", "names cannot start with a digit"),
+    ("E0108", "el número '{0}' está fuera de rango", "number '{0}' is out of range"),
+    ("E0108.note", "los enteros son de 64 bits con signo y los decimales deben ser finitos", "integers are signed 64-bit and floats must be finite"),
+    ("E0109.unclosed", "interpolación sin '}' de cierre en la misma línea", "interpolation has no closing '}' on the same line"),
+    ("E0109.stray_close", "'}' sin interpolación abierta; usa '}}' para una llave literal", "'}' without an open interpolation; use '}}' for a literal brace"),
+
+    // Sintaxis
+    ("label.found", "se encontró '{0}'", "found '{0}'"),
+    ("label.eof", "se encontró el final del archivo", "found end of file"),
+    ("label.newline", "se encontró un salto de línea", "found a line break"),
+    ("E0201.block_end", "se esperaba 'end' para cerrar '{0}'", "expected 'end' to close '{0}'"),
+    ("E0201.let_name", "se esperaba el nombre de la variable después de 'let'", "expected a variable name after 'let'"),
+    ("E0201.fn_open_paren", "se esperaba '(' después del nombre de la función", "expected '(' after the function name"),
+    ("E0201.sure_value", "una variable 'sure' necesita un valor: 'let PI sure = 3.1416'", "a 'sure' variable needs a value: 'let PI sure = 3.1416'"),
+    ("E0201.del_name", "se esperaba el nombre de la variable después de 'del'", "expected a variable name after 'del'"),
+    ("E0201.old_message", "se esperaba un mensaje de texto en 'old(...)'", "expected a text message in 'old(...)'"),
+    ("E0201.old_close_paren", "se esperaba ')' después del mensaje de 'old'", "expected ')' after the 'old' message"),
+    ("E0201.old_target", "se esperaba 'let' o 'fn' después de 'old'", "expected 'let' or 'fn' after 'old'"),
+    ("E0201.param_name", "se esperaba el nombre de un parámetro", "expected a parameter name"),
+    ("E0201.fn_close_paren", "se esperaba ')' después de los parámetros", "expected ')' after the parameters"),
+    ("E0201.brace_open", "se esperaba '{' para abrir el bloque", "expected '{' to open the block"),
+    ("E0201.brace_close", "se esperaba '}' para cerrar el bloque", "expected '}' to close the block"),
+    ("E0201.do_while", "se esperaba 'while' después del cuerpo de 'do'", "expected 'while' after the 'do' body"),
+    ("E0201.for_init", "se esperaba ';' después de la inicialización de 'for'", "expected ';' after the 'for' initializer"),
+    ("E0201.for_condition", "se esperaba ';' después de la condición de 'for'", "expected ';' after the 'for' condition"),
+    ("E0201.for_close_paren", "se esperaba ')' después del encabezado de 'for'", "expected ')' after the 'for' header"),
+    ("E0201.ternary_colon", "se esperaba ':' en la expresión ternaria", "expected ':' in the ternary expression"),
+    ("E0201.call_close_paren", "se esperaba ')' después de los argumentos", "expected ')' after the arguments"),
+    ("E0201.group_close_paren", "se esperaba ')' después de la expresión", "expected ')' after the expression"),
+    ("E0201.interpolation_close", "se esperaba '}' para cerrar la interpolación", "expected '}' to close the interpolation"),
+    ("E0201.statement_end", "se esperaba un salto de línea o ';' al final de la instrucción", "expected a line break or ';' after the statement"),
+    ("E0201.ui_attribute", "se esperaba un atributo 'nombre=valor' en el elemento de la interfaz", "expected a 'name=value' attribute on the UI element"),
+    ("E0201.type_name", "se esperaba un tipo (como 'int', 'list<string>' o el nombre de un 'type')", "expected a type (such as 'int', 'list<string>' or the name of a 'type')"),
+    ("E0201.type_args_close", "se esperaba '>' para cerrar los parámetros del tipo", "expected '>' to close the type parameters"),
+    ("E0201.property_name", "se esperaba el nombre de una propiedad después de '.'", "expected a property name after '.'"),
+    ("E0202", "se esperaba una expresión", "expected an expression"),
+    ("E0203", "objetivo de asignación inválido", "invalid assignment target"),
+    ("E0204", "cierre de bloque inesperado, no hay un bloque abierto", "unexpected block close, no block is open"),
+    ("E0205", "número inválido", "invalid number"),
+    ("E0206", "'{0}' es una palabra reservada en el idioma '{1}'", "'{0}' is a reserved keyword in language '{1}'"),
+    ("E0206.help", "renombra el identificador antes de convertir el archivo", "rename the identifier before converting the file"),
+
+    // Ejecución
+    ("E0301", "variable no definida: '{0}'", "undefined variable: '{0}'"),
+    ("E0301.assign", "no se puede asignar a '{0}': la variable no fue declarada", "cannot assign to '{0}': the variable was never declared"),
+    ("E0301.delete", "no se puede borrar '{0}': la variable no fue declarada", "cannot delete '{0}': the variable was never declared"),
+    ("E0302.negate", "el operador '-' requiere un número, se recibió '{0}'", "operator '-' expects a number, got '{0}'"),
+    ("E0302.binary", "operación '{0}' no soportada entre '{1}' y '{2}'", "unsupported operation '{0}' between '{1}' and '{2}'"),
+    ("E0302.len", "len() no acepta valores de tipo '{0}'", "len() does not accept values of type '{0}'"),
+    ("E0302.push", "push() espera una lista, se recibió '{0}'", "push() expects a list, got '{0}'"),
+    ("E0303.division", "división entre cero", "division by zero"),
+    ("E0303.modulo", "módulo entre cero", "modulo by zero"),
+    ("E0304", "solo se pueden llamar funciones, se recibió '{0}'", "only functions can be called, got '{0}'"),
+    ("E0305", "la función '{0}' espera {1} argumento(s), se recibieron {2}", "function '{0}' expects {1} argument(s), got {2}"),
+    ("E0306", "se excedió la profundidad máxima de llamadas", "maximum call depth exceeded"),
+    ("E0306.help", "revisa si la función se llama a sí misma sin un caso base", "check whether the function calls itself without a base case"),
+    ("E0307.get", "el valor de tipo '{0}' no tiene la propiedad '{1}'", "value of type '{0}' has no property '{1}'"),
+    ("E0307.set", "no se puede asignar la propiedad '{0}' en un valor de tipo '{1}'", "cannot set property '{0}' on a value of type '{1}'"),
+    ("E0308", "no se puede iterar sobre un valor de tipo '{0}'", "cannot iterate over a value of type '{0}'"),
+    ("E0309.break", "'break' fuera de un bucle", "'break' outside of a loop"),
+    ("E0309.continue", "'continue' fuera de un bucle", "'continue' outside of a loop"),
+    ("E0310", "función integrada desconocida: '{0}'", "unknown builtin function: '{0}'"),
+    ("E0311", "'{0}' necesita la interfaz gráfica, que el intérprete todavía no soporta", "'{0}' needs the graphical interface, which the interpreter does not support yet"),
+    ("E0312", "la declaración de '{0}' no está en la resolución de nombres", "the declaration of '{0}' is not in the name resolution"),
+    ("E0312.help", "resuelve el mismo programa que se ejecuta (con 'resolve' o 'check_with_diagnostics')", "resolve the same program that is run (with 'resolve' or 'check_with_diagnostics')"),
+
+    // Tipos
+    ("E0401", "tipos incompatibles: se esperaba '{0}', se encontró '{1}'", "mismatched types: expected '{0}', found '{1}'"),
+    ("E0401.label", "esto es '{0}'", "this is '{0}'"),
+    ("E0401.declared", "el tipo se declaró aquí", "type declared here"),
+    ("E0402", "no se puede aplicar '{0}' a '{1}' y '{2}'", "cannot apply '{0}' to '{1}' and '{2}'"),
+    ("E0402.negate", "el operador '-' requiere un número, se encontró '{0}'", "operator '-' expects a number, found '{0}'"),
+    ("E0403", "la condición es de tipo '{0}', no 'bool'", "condition has type '{0}', not 'bool'"),
+    ("E0403.function", "una función siempre es verdadera: ¿faltan los paréntesis de la llamada?", "a function is always true: are the call parentheses missing?"),
+    ("E0403.null", "'none' siempre es falso", "'none' is always false"),
+    ("E0404", "'{0}' espera {1} argumento(s), se pasaron {2}", "'{0}' expects {1} argument(s), {2} given"),
+    ("E0405", "'{0}' lleva {1} parámetro(s) de tipo, se escribieron {2}", "'{0}' takes {1} type parameter(s), {2} given"),
+    ("E0406", "solo se pueden llamar funciones, se encontró '{0}'", "only functions can be called, found '{0}'"),
+    ("E0407", "no se puede recorrer un valor de tipo '{0}'", "cannot iterate over a value of type '{0}'"),
+
+    // Nombres
+    ("E0501", "nombre no definido: '{0}'", "undefined name: '{0}'"),
+    ("E0501.assign", "no se puede asignar a '{0}': la variable no fue declarada", "cannot assign to '{0}': the variable was never declared"),
+    ("E0501.delete", "no se puede borrar '{0}': la variable no fue declarada", "cannot delete '{0}': the variable was never declared"),
+    ("E0501.help", "¿quisiste decir '{0}'?", "did you mean '{0}'?"),
+    ("E0502", "'{0}' ya está declarado en este ámbito", "'{0}' is already declared in this scope"),
+    ("E0502.previous", "declarado antes aquí", "previously declared here"),
+    ("E0502.help", "usa otro nombre, o asigna sin 'let': '{0} = ...'", "use another name, or assign without 'let': '{0} = ...'"),
+    ("E0503", "'{0}' oculta una declaración de un ámbito exterior", "'{0}' shadows a declaration from an outer scope"),
+    ("E0503.outer", "declarado aquí", "declared here"),
+    ("E0504", "no se puede modificar '{0}': se declaró con 'sure'", "cannot modify '{0}': it was declared 'sure'"),
+    ("E0504.delete", "no se puede borrar '{0}': se declaró con 'sure'", "cannot delete '{0}': it was declared 'sure'"),
+    ("E0504.declared", "declarado con 'sure' aquí", "declared 'sure' here"),
+    ("E0504.help", "quita 'sure' de la declaración si la variable debe cambiar", "remove 'sure' from the declaration if the variable must change"),
+    ("E0505", "'{0}' está obsoleto", "'{0}' is deprecated"),
+    ("E0505.declared", "marcado con 'old' aquí", "marked 'old' here"),
+    ("E0506", "la llamada a '{0}' usa '{1}' antes de que se declare", "the call to '{0}' uses '{1}' before it is declared"),
+    ("E0506.used", "se usa aquí", "used here"),
+    ("E0506.declared", "declarado después de la llamada", "declared after the call"),
+    ("E0506.help", "mueve la llamada debajo de la declaración", "move the call below the declaration"),
+
+    // CLI
+    ("cli.usage", "Uso: yuka [--lang es|en] [--deny-old] [check | fmt [--keywords es|en] | migrate | tokens [--format table|json|jsonl] | ast [--format tree|json|jsonl]] <archivo.yuka>", "Usage: yuka [--lang es|en] [--deny-old] [check | fmt [--keywords es|en] | migrate | tokens [--format table|json|jsonl] | ast [--format tree|json|jsonl]] <file.yuka>"),
+    ("cli.read_error", "no se pudo leer el archivo '{0}': {1}", "could not read file '{0}': {1}"),
+    ("cli.unknown_lang", "idioma desconocido '{0}' (se aceptan 'es' y 'en')", "unknown language '{0}' (expected 'es' or 'en')"),
+    ("cli.unknown_format", "formato desconocido '{0}' (se aceptan 'table', 'tree', 'json' y 'jsonl')", "unknown format '{0}' (expected 'table', 'tree', 'json' or 'jsonl')"),
+];
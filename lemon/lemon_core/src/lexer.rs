@@ -1,304 +1,1456 @@
-use crate::token::{Token, TokenType};
-use crate::error::LemonError;
+use crate::token::{Interner, Lexeme, LosslessToken, Token, TokenType, Trivia, TriviaKind};
+use crate::diagnostic::{codes, Diagnostic};
+use crate::grammar::{continues_expression, Comment, Comparator, Keyword, Symbol, COMPARATORS, LOGICAL_SYMBOLS, OPERATORS, SYMBOLS, UI_CONTAINERS};
+use crate::i18n::{tr, Locale};
+use crate::span::Span;
+use std::collections::VecDeque;
+use std::sync::LazyLock;
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::GraphemeCursor;
+use unicode_xid::UnicodeXID;
 
-/// Función principal: convierte el código fuente en una lista de tokens
-pub fn tokenize(code: &str) -> Result<Vec<Token>, LemonError> {
+/// Posición dentro del código fuente: byte, línea y columna.
+///
+/// La columna cuenta grupos de grafemas (lo que el lector ve como un
+/// carácter: `ñ` cuenta uno aunque se escriba `n` + tilde combinante), igual
+/// que `SourceMap::location`; el byte exacto queda en `offset` y en los `Span`.
+#[derive(Debug, Clone, Copy)]
+struct Position {
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+/// Recorre el código carácter por carácter llevando la cuenta de la posición.
+/// Todo avance pasa por `bump` (o `bump_ascii_while`), así los saltos de
+/// línea dentro de cadenas y comentarios también actualizan la línea.
+///
+/// Trabaja directamente sobre los bytes de `code`: mirar adelante no copia
+/// ni clona nada, y el texto ASCII (la gran mayoría) no necesita decodificarse.
+struct Cursor<'a> {
+    code: &'a str,
+    pos: Position,
+    end: usize,       // Byte donde termina el rango que recorre
+    prev_ascii: bool, // El último carácter consumido era ASCII
+}
+
+impl<'a> Cursor<'a> {
+    fn new(code: &'a str) -> Self {
+        Self::at(code, Position { offset: 0, line: 1, column: 1 }, code.len())
+    }
+
+    /// Cursor que recorre solo `code[pos.offset..end]`, con posiciones
+    /// absolutas. Se usa para las expresiones dentro de una interpolación.
+    fn at(code: &'a str, pos: Position, end: usize) -> Self {
+        Cursor { code, pos, end, prev_ascii: true }
+    }
+
+    /// Texto que falta recorrer.
+    fn rest(&self) -> &'a str {
+        &self.code[self.pos.offset..self.end]
+    }
+
+    /// Byte `n` posiciones más adelante, sin consumir nada.
+    fn byte_at(&self, n: usize) -> Option<u8> {
+        let index = self.pos.offset + n;
+        if index < self.end { Some(self.code.as_bytes()[index]) } else { None }
+    }
+
+    /// Carácter actual, sin consumirlo.
+    fn peek(&self) -> Option<char> {
+        match self.byte_at(0)? {
+            b if b.is_ascii() => Some(b as char),
+            _ => self.rest().chars().next(),
+        }
+    }
+
+    /// Carácter siguiente al actual, sin consumir nada.
+    fn peek_second(&self) -> Option<char> {
+        self.peek_nth(1)
+    }
+
+    /// Carácter `n` posiciones más adelante (`0` es el actual), sin consumir nada.
+    fn peek_nth(&self, n: usize) -> Option<char> {
+        // Si los bytes hasta ahí son ASCII, el carácter `n` es el byte `n`
+        let bytes = &self.code.as_bytes()[self.pos.offset..self.end];
+        if bytes.iter().take(n + 1).all(u8::is_ascii) {
+            bytes.get(n).map(|&b| b as char)
+        } else {
+            self.rest().chars().nth(n)
+        }
+    }
+
+    /// Consume un carácter y actualiza la posición. La columna solo avanza
+    /// si el carácter empieza un grafema nuevo (una marca combinante no).
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        let starts_grapheme = (self.prev_ascii && c.is_ascii()) || self.is_grapheme_boundary(self.pos.offset);
+        self.pos.offset += c.len_utf8();
+        self.prev_ascii = c.is_ascii();
+        if c == '\n' {
+            self.pos.line += 1;
+            self.pos.column = 1;
+        } else if starts_grapheme {
+            self.pos.column += 1;
+        }
+        Some(c)
+    }
+
+    /// Consume bytes ASCII mientras cumplan `keep` (que nunca debe aceptar
+    /// `\n`). Cada uno es un grafema propio, salvo el primero si viene
+    /// después de un carácter no ASCII, que se consume con `bump`.
+    fn bump_ascii_while(&mut self, mut keep: impl FnMut(u8) -> bool) {
+        if !self.prev_ascii {
+            match self.byte_at(0) {
+                Some(b) if b.is_ascii() && keep(b) => self.bump(),
+                _ => return,
+            };
+        }
+        let start = self.pos.offset;
+        let bytes = &self.code.as_bytes()[start..self.end];
+        let count = bytes.iter().take_while(|&&b| b.is_ascii() && keep(b)).count();
+        self.pos.offset += count;
+        self.pos.column += count;
+    }
+
+    /// Consume `count` bytes ASCII que no son `\n` (por ejemplo, un operador).
+    fn bump_ascii(&mut self, count: usize) {
+        let mut left = count;
+        self.bump_ascii_while(|_| {
+            let keep = left > 0;
+            left = left.saturating_sub(1);
+            keep
+        });
+    }
+
+    /// Indica si en el byte `offset` empieza un grupo de grafemas.
+    fn is_grapheme_boundary(&self, offset: usize) -> bool {
+        GraphemeCursor::new(offset, self.code.len(), true)
+            .is_boundary(self.code, 0)
+            .unwrap_or(true)
+    }
+}
+
+/// Construye un token que empieza en `start` y termina en el byte `end`.
+fn make_token(token_type: TokenType, value: Lexeme, start: Position, end: usize) -> Token {
+    Token {
+        token_type,
+        value,
+        line: start.line,
+        column: start.column,
+        span: Span::new(start.offset, end),
+    }
+}
+
+/// Error léxico en `start`, cubriendo hasta el byte `end`.
+/// El mensaje sale del catálogo, con el código como clave.
+fn lex_error(code: &'static str, start: Position, end: usize) -> Diagnostic {
+    Diagnostic::error(code, &tr(code, &[])).with_span(Span::new(start.offset, end))
+}
+
+/// Prefijos del pragma que elige el idioma de las palabras clave de un archivo.
+const PRAGMA_PREFIXES: [&str; 2] = ["lang:", "idioma:"];
+
+/// Busca el pragma de idioma (`// lang: es` o `// idioma: es`) entre los
+/// comentarios de línea del inicio del archivo, antes de cualquier código.
+/// Devuelve el idioma y el rango del comentario, sin el salto de línea.
+pub fn keyword_pragma(code: &str) -> Option<(Locale, Span)> {
+    let mut offset = 0;
+    for line in code.split_inclusive('\n') {
+        let text = line.trim_end_matches(['\n', '\r']);
+        let start = offset + (text.len() - text.trim_start().len());
+        offset += line.len();
+
+        let trimmed = text.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let comment = trimmed.strip_prefix("//")?.trim();
+        let tag = PRAGMA_PREFIXES.iter().find_map(|prefix| comment.strip_prefix(prefix));
+        if let Some(locale) = tag.and_then(|tag| Locale::from_tag(tag.trim())) {
+            return Some((locale, Span::new(start, start + trimmed.len())));
+        }
+    }
+    None
+}
+
+/// Función principal: convierte el código fuente en una lista de tokens.
+///
+/// El análisis nunca se detiene en un error: cada problema se agrega a la
+/// lista de diagnósticos y, si hace falta, se emite un token `Unknown` en su
+/// lugar. Así una sola ejecución informa todos los errores léxicos.
+///
+/// Las palabras clave se reconocen en inglés, o también en español si el
+/// archivo empieza con el pragma `// lang: es` (ver `keyword_pragma`).
+pub fn tokenize(code: &str) -> (Vec<Token>, Vec<Diagnostic>) {
+    let keywords = keyword_pragma(code).map_or(Locale::English, |(locale, _)| locale);
+    let mut lx = Lexing::new(keywords);
     let mut tokens = Vec::new();            // Vector donde se guardan los tokens generados
-    let mut current = String::new();        // Acumula caracteres para identificadores y palabras clave
-    let mut chars = code.chars().peekable(); // Permite mirar el siguiente carácter sin consumirlo
-    let mut line = 1;
-    let mut column = 1;
-    let mut start_column = column;
+    let mut cursor = Cursor::new(code);     // Permite mirar el siguiente carácter sin consumirlo
+
+    lex_range(&mut cursor, &mut lx, &mut tokens);
 
-    while let Some(&ch) = chars.peek() {
+    // Agregar token de fin de archivo
+    tokens.push(make_token(TokenType::EOF, Lexeme::from_static("<EOF>"), cursor.pos, cursor.pos.offset));
+
+    (tokens, lx.diagnostics)
+}
+
+/// Modo sin pérdida: los mismos tokens que [`tokenize`], cada uno con su
+/// texto original y la trivia (espacios, saltos de línea y comentarios) que
+/// lo rodea. El token `EOF` lleva la trivia del final del archivo.
+///
+/// ```
+/// # use lemon_core::lexer::tokenize_lossless;
+/// # use lemon_core::token::to_source;
+/// let code = "// saludo\nlet x = 1 /* uno */\r\nprint \"{x}\"\n";
+/// let (tokens, _) = tokenize_lossless(code);
+/// assert_eq!(to_source(&tokens), code);
+/// assert_eq!(tokens[0].leading[0].text, "// saludo");
+/// // El comentario al final de la línea queda con el `1`
+/// let kinds: Vec<_> = tokens[3].trailing.iter().map(|t| t.text.as_str()).collect();
+/// assert_eq!(kinds, [" ", "/* uno */"]);
+/// assert_eq!(tokens[4].text, "\r\n"); // Salto de línea significativo
+/// ```
+pub fn tokenize_lossless(code: &str) -> (Vec<LosslessToken>, Vec<Diagnostic>) {
+    let (tokens, diagnostics) = tokenize(code);
+    let mut lossless: Vec<LosslessToken> = Vec::with_capacity(tokens.len());
+    let mut previous_end = 0;
+
+    for token in tokens {
+        let mut leading = split_trivia(&code[previous_end..token.span.start], previous_end);
+
+        // Lo que sigue al token anterior en su misma línea es trivia final de ese token
+        if let Some(previous) = lossless.last_mut() {
+            let line_end = leading
+                .iter()
+                .position(|trivia| trivia.kind == TriviaKind::Newline)
+                .map_or(leading.len(), |i| i + 1);
+            previous.trailing = leading.drain(..line_end).collect();
+        }
+
+        previous_end = token.span.end;
+        lossless.push(LosslessToken {
+            text: code[token.span.start..token.span.end].to_string(),
+            token,
+            leading,
+            trailing: Vec::new(),
+        });
+    }
+
+    (lossless, diagnostics)
+}
+
+/// Divide el texto entre dos tokens (que empieza en el byte `base`) en trivia.
+fn split_trivia(text: &str, base: usize) -> Vec<Trivia> {
+    let mut trivia = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let (kind, len) = if rest.starts_with("//") {
+            (TriviaKind::Comment(Comment::LineComment), rest.find(['\r', '\n']).unwrap_or(rest.len()))
+        } else if let Some(body) = rest.strip_prefix("/*") {
+            // Un comentario sin cerrar llega hasta el final del archivo
+            (TriviaKind::Comment(Comment::BlockComment), body.find("*/").map_or(rest.len(), |i| i + 4))
+        } else if rest.starts_with("\r\n") {
+            (TriviaKind::Newline, 2)
+        } else if rest.starts_with('\n') {
+            (TriviaKind::Newline, 1)
+        } else {
+            // Espacios hasta el próximo salto de línea o comentario
+            let len = rest
+                .char_indices()
+                .skip(1)
+                .find(|&(i, c)| c == '\n' || c == '/' || rest[i..].starts_with("\r\n"))
+                .map_or(rest.len(), |(i, _)| i);
+            (TriviaKind::Whitespace, len)
+        };
+        let start = base + (text.len() - rest.len());
+        trivia.push(Trivia { kind, text: rest[..len].to_string(), span: Span::new(start, start + len) });
+        rest = &rest[len..];
+    }
+    trivia
+}
+
+// ============================
+// 🔁 Lexer incremental
+// ============================
+
+/// Construcción sin terminar al final de la entrada recibida (ver [`Lexer::state`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LexState {
+    /// Todo lo recibido está cerrado: se puede analizar y ejecutar.
+    Complete,
+    /// Falta la comilla de cierre de una cadena o bloque de texto.
+    InString,
+    /// Falta el `*/` de un comentario multilínea.
+    InComment,
+    /// Quedan bloques (`end` o `}`), paréntesis o corchetes sin cerrar;
+    /// el número indica cuántos.
+    InBlock(usize),
+}
+
+/// Bloque abierto que sigue el [`Lexer`] para informar `LexState::InBlock`.
+#[derive(Debug, Clone, Copy)]
+struct OpenBlock {
+    keyword: Option<Keyword>,   // Palabra que lo abrió (`None` para una `{` suelta)
+    braces: bool,               // Se cierra con `}` en lugar de `end`
+    header_done: bool,          // Ya terminó la línea del encabezado
+    body: bool,                 // Ya empezó el cuerpo: una `{` no lo convierte al estilo con llaves
+    contextual: bool,           // Abierto por una palabra contextual (`window`, `tick`, ...)
+}
+
+/// Lexer incremental: entrega los tokens de a uno, como iterador, y acepta
+/// más código después de haber empezado.
+///
+/// Analiza una línea completa por vez. Una línea que termina dentro de una
+/// cadena o de un comentario multilínea se retiene hasta que llegue el
+/// cierre (o hasta `finish`), así los tokens entregados nunca cambian. Sin
+/// más líneas completas, `next` devuelve `None`; después de `push_str` el
+/// iterador vuelve a entregar tokens. El token `EOF` solo se emite después
+/// de `finish`.
+///
+/// Los errores léxicos se entregan como `Err`, antes del token al que
+/// corresponden (que sigue llegando como `Unknown` si hace falta). Con la
+/// entrada completa, los tokens y diagnósticos son los mismos que los de
+/// [`tokenize`].
+///
+/// ```
+/// # use lemon_core::lexer::{LexState, Lexer};
+/// # use lemon_core::token::TokenType;
+/// let mut lexer = Lexer::new("fn doble(x)\n");
+/// assert_eq!(lexer.by_ref().filter_map(Result::ok).count(), 6); // fn doble ( x ) \n
+/// assert_eq!(lexer.state(), LexState::InBlock(1));
+///
+/// lexer.push_str("  back x * 2\nend\n");
+/// let rest: Vec<_> = lexer.by_ref().filter_map(Result::ok).collect();
+/// assert_eq!(rest.last().unwrap().value, "\n");
+/// assert_eq!(lexer.state(), LexState::Complete);
+///
+/// lexer.push_str("print \"sin cerrar\n");
+/// assert!(lexer.next().is_none());
+/// assert_eq!(lexer.state(), LexState::InString);
+///
+/// lexer.finish();
+/// let tail: Vec<_> = lexer.collect();
+/// assert!(tail[0].is_ok());  // `print`
+/// assert!(tail[1].is_err()); // cadena sin comilla de cierre
+/// assert_eq!(tail.last().unwrap().as_ref().unwrap().token_type, TokenType::EOF);
+/// ```
+pub struct Lexer {
+    source: String,
+    pos: Position,                                  // Inicio de lo que falta analizar
+    lx: Lexing,
+    queue: VecDeque<Result<Token, Diagnostic>>,     // Analizado pero todavía no entregado
+    unclosed: Option<LexState>,                     // Cadena o comentario retenido hasta la próxima línea
+    blocks: Vec<OpenBlock>,
+    interpolations: usize,                          // Interpolaciones abiertas (sus llaves no son bloques)
+    closed_do: bool,                                // Se acaba de cerrar `do { ... }`: el `while` no abre un bloque
+    finished: bool,
+    done: bool,                                     // Ya se entregó el `EOF`
+}
+
+impl Lexer {
+    /// Crea un lexer con el código inicial (puede estar vacío).
+    pub fn new(code: &str) -> Self {
+        Lexer {
+            source: code.to_string(),
+            pos: Position { offset: 0, line: 1, column: 1 },
+            lx: Lexing::new(Locale::English),
+            queue: VecDeque::new(),
+            unclosed: None,
+            blocks: Vec::new(),
+            interpolations: 0,
+            closed_do: false,
+            finished: false,
+            done: false,
+        }
+    }
+
+    /// Agrega código al final de la entrada (antes de `finish`). Las
+    /// posiciones siguen siendo relativas al inicio del primer fragmento.
+    pub fn push_str(&mut self, more: &str) {
+        self.source.push_str(more);
+    }
+
+    /// Marca el final de la entrada: se analiza la última línea (aunque no
+    /// termine en salto de línea) y después se entrega el `EOF`.
+    pub fn finish(&mut self) {
+        self.finished = true;
+    }
+
+    /// Todo el código recibido hasta ahora.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Estado al final de lo analizado hasta ahora: dentro de una cadena, de
+    /// un comentario o de un bloque, o completo. Un REPL consume los tokens
+    /// disponibles y con este estado decide si pedir otra línea.
+    ///
+    /// Los bloques se cuentan por sus palabras (`if`, `while`, `for`, `fn`,
+    /// `try`, `do` y los contenedores de la interfaz) y por las llaves, sin
+    /// analizar la sintaxis: es una estimación que coincide con el parser en
+    /// código bien formado.
+    pub fn state(&self) -> LexState {
+        if let Some(state) = self.unclosed {
+            return state;
+        }
+        match self.blocks.len() + self.lx.nesting {
+            0 => LexState::Complete,
+            depth => LexState::InBlock(depth),
+        }
+    }
+
+    /// Analiza la próxima línea completa. Devuelve `false` si no hay nada
+    /// que analizar o si la línea quedó retenida.
+    fn lex_next_line(&mut self) -> bool {
+        let start = self.pos.offset;
+        let rest = &self.source[start..];
+        let available = match rest.rfind('\n') {
+            _ if self.finished => self.source.len(),
+            Some(i) => start + i + 1,
+            None => return false,
+        };
+        if available == start {
+            return false;
+        }
+        let line_end = rest.find('\n').map_or(available, |i| start + i + 1);
+
+        // Una cadena o comentario de varias líneas se vuelve a intentar con todo lo disponible
+        self.lex_chunk(line_end) || (available > line_end && self.lex_chunk(available))
+    }
+
+    /// Analiza `source[pos..end]` y encola sus tokens y diagnósticos, salvo
+    /// que termine dentro de una cadena o comentario que todavía puede cerrarse.
+    fn lex_chunk(&mut self, end: usize) -> bool {
+        // El pragma de idioma vale mientras no haya código antes
+        if self.lx.last.is_none() {
+            self.lx.keywords = keyword_pragma(&self.source[..end]).map_or(Locale::English, |(locale, _)| locale);
+        }
+
+        let nesting = self.lx.nesting;
+        let mut cursor = Cursor::at(&self.source[..end], self.pos, end);
+        let mut tokens = Vec::new();
+        lex_range(&mut cursor, &mut self.lx, &mut tokens);
+
+        let unclosed = self.lx.diagnostics.iter().find_map(|d| {
+            let at_end = d.span().is_some_and(|span| span.end == end);
+            match d.code {
+                codes::UNCLOSED_STRING if at_end => Some(LexState::InString),
+                codes::UNCLOSED_COMMENT if at_end => Some(LexState::InComment),
+                _ => None,
+            }
+        });
+        if let Some(state) = unclosed
+            && (end < self.source.len() || !self.finished)
+        {
+            self.lx.diagnostics.clear();
+            self.lx.nesting = nesting;
+            self.unclosed = Some(state);
+            return false;
+        }
+
+        self.pos = cursor.pos;
+        self.unclosed = None;
+        self.track_blocks(&tokens);
+        if let Some(last) = tokens.last() {
+            self.lx.last = Some(last.token_type);
+        }
+
+        // Cada diagnóstico va antes del primer token que no empieza antes que él
+        let mut diagnostics = self.lx.diagnostics.drain(..).peekable();
+        for token in tokens {
+            while let Some(d) = diagnostics.next_if(|d| d.span().is_some_and(|span| span.start <= token.span.start)) {
+                self.queue.push_back(Err(d));
+            }
+            self.queue.push_back(Ok(token));
+        }
+        self.queue.extend(diagnostics.map(Err));
+        true
+    }
+
+    /// Actualiza los bloques abiertos con los tokens de una línea.
+    fn track_blocks(&mut self, tokens: &[Token]) {
+        let mut previous = self.lx.last;
+        for token in tokens {
+            let token_type = token.token_type;
+            match token_type {
+                TokenType::InterpolationStart => self.interpolations += 1,
+                TokenType::InterpolationEnd => self.interpolations = self.interpolations.saturating_sub(1),
+                _ if self.interpolations > 0 => continue,
+                _ => {}
+            }
+
+            // `window = 1` o `tick.x`: la palabra contextual era un nombre
+            if let Some(top) = self.blocks.last()
+                && top.contextual
+                && !top.header_done
+                && !top.body
+                && previous == Some(TokenType::Identifier)
+                && continues_expression(token_type)
+            {
+                self.blocks.pop();
+            }
+
+            let closed_do = std::mem::take(&mut self.closed_do);
+            match token_type {
+                TokenType::Newline | TokenType::Symbol(Symbol::Semicolon) => {
+                    if let Some(top) = self.blocks.last_mut() {
+                        top.header_done = true;
+                    }
+                    self.closed_do = closed_do && token_type == TokenType::Newline;
+                }
+                TokenType::Symbol(Symbol::OpenBrace) => match self.blocks.last_mut() {
+                    Some(top) if !top.braces && !top.body => {
+                        top.braces = true;
+                        top.body = true;
+                    }
+                    _ => self.open(None, true),
+                },
+                TokenType::Symbol(Symbol::CloseBrace) => {
+                    if self.blocks.last().is_some_and(|top| top.braces) {
+                        let block = self.blocks.pop();
+                        self.closed_do = block.is_some_and(|block| block.keyword == Some(Keyword::Do));
+                    }
+                }
+                TokenType::Keyword(Keyword::End) => {
+                    if self.blocks.last().is_some_and(|top| !top.braces) {
+                        self.blocks.pop();
+                    }
+                }
+                TokenType::Keyword(Keyword::While) => {
+                    // El `while` de `do ... while cond end` no abre otro bloque
+                    let do_body = self.blocks.last().is_some_and(|top| top.keyword == Some(Keyword::Do) && !top.braces);
+                    self.start_body();
+                    if !do_body && !closed_do {
+                        self.open(Some(Keyword::While), false);
+                    }
+                }
+                TokenType::Keyword(
+                    keyword @ (Keyword::If | Keyword::For | Keyword::Fn | Keyword::Try | Keyword::Do | Keyword::Wrap),
+                ) => {
+                    self.start_body();
+                    self.open(Some(keyword), false);
+                }
+                TokenType::Identifier if statement_start(previous) => {
+                    self.start_body();
+                    let container = Keyword::parse(&token.value)
+                        .filter(|kw| kw.is_contextual() && UI_CONTAINERS.contains(kw));
+                    if let Some(keyword) = container {
+                        self.open(Some(keyword), false);
+                        if let Some(top) = self.blocks.last_mut() {
+                            top.contextual = true;
+                        }
+                    }
+                }
+                _ => self.start_body(),
+            }
+            previous = Some(token_type);
+        }
+    }
+
+    fn open(&mut self, keyword: Option<Keyword>, braces: bool) {
+        self.blocks.push(OpenBlock { keyword, braces, header_done: false, body: braces, contextual: false });
+    }
+
+    /// Un token después de la línea del encabezado empieza el cuerpo de un bloque `end`.
+    fn start_body(&mut self) {
+        if let Some(top) = self.blocks.last_mut()
+            && top.header_done
+        {
+            top.body = true;
+        }
+    }
+}
+
+/// Indica si un token puede ser el primero de una instrucción.
+fn statement_start(previous: Option<TokenType>) -> bool {
+    matches!(
+        previous,
+        None | Some(TokenType::Newline | TokenType::Symbol(Symbol::Semicolon | Symbol::OpenBrace))
+    )
+}
+
+impl Iterator for Lexer {
+    type Item = Result<Token, Diagnostic>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.queue.pop_front() {
+                return Some(item);
+            }
+            if self.done {
+                return None;
+            }
+            if self.lex_next_line() {
+                continue;
+            }
+            if !self.finished {
+                return None;
+            }
+            self.done = true;
+            return Some(Ok(make_token(TokenType::EOF, Lexeme::from_static("<EOF>"), self.pos, self.pos.offset)));
+        }
+    }
+}
+
+/// Estado que comparten las funciones del lexer: idioma de las palabras
+/// clave, textos ya vistos y diagnósticos encontrados.
+struct Lexing {
+    keywords: Locale,
+    interner: Interner,
+    diagnostics: Vec<Diagnostic>,
+    nesting: usize,                 // Paréntesis y corchetes abiertos
+    last: Option<TokenType>,        // Último token de los rangos ya analizados (ver `Lexer`)
+}
+
+impl Lexing {
+    fn new(keywords: Locale) -> Self {
+        Lexing { keywords, interner: Interner::default(), diagnostics: Vec::new(), nesting: 0, last: None }
+    }
+}
+
+/// Convierte en tokens todo lo que recorre `cursor` (el archivo completo o
+/// el interior de una interpolación), sin agregar el token `EOF`.
+///
+/// Las palabras no se copian mientras se leen: se recuerda dónde empiezan y
+/// al terminar se toma el texto directamente de `cursor.code`.
+fn lex_range(cursor: &mut Cursor, lx: &mut Lexing, tokens: &mut Vec<Token>) {
+    let mut word: Option<Position> = None;  // Inicio de la palabra (identificador o palabra clave) en curso
+    let mut nesting = lx.nesting;           // Paréntesis y corchetes abiertos
+
+    while let Some(ch) = cursor.peek() {
         // =========================
         // Comentario de una línea
         // =========================
-        if ch == '/' && chars.clone().nth(1) == Some('/') {
-            while let Some(&c) = chars.peek() {
-                chars.next();
-                column += 1;
-                if c == '\n' {
+        if ch == '/' && cursor.peek_second() == Some('/') {
+            flush_word(&mut word, cursor, lx, tokens);
+            // El salto de línea no es parte del comentario: puede terminar una instrucción
+            cursor.bump_ascii_while(|b| b != b'\n' && b != b'\r');
+            while let Some(c) = cursor.peek() {
+                if c == '\n' || (c == '\r' && cursor.peek_second() == Some('\n')) {
                     break;
                 }
+                cursor.bump();
+                cursor.bump_ascii_while(|b| b != b'\n' && b != b'\r');
             }
-            line += 1;
-            column = 1;
             continue;
         }
 
         // =========================
         // Comentario multilínea
         // =========================
-        if ch == '/' && chars.clone().nth(1) == Some('*') {
-            chars.next(); // consume '/'
-            chars.next(); // consume '*'
-            column += 2;
+        if ch == '/' && cursor.peek_second() == Some('*') {
+            flush_word(&mut word, cursor, lx, tokens);
+            let comment_start = cursor.pos;
+            cursor.bump(); // consume '/'
+            cursor.bump(); // consume '*'
             let mut closed = false;
-            while let Some(c) = chars.next() {
-                if c == '*' && chars.peek() == Some(&'/') {
-                    chars.next(); // consume '/'
-                    column += 2;
+            while let Some(c) = cursor.bump() {
+                if c == '*' && cursor.peek() == Some('/') {
+                    cursor.bump(); // consume '/'
                     closed = true;
                     break;
                 }
-                if c == '\n' {
-                    line += 1;
-                    column = 1;
-                } else {
-                    column += 1;
-                }
             }
             if !closed {
-                return Err(LemonError::new("Unclosed multiline comment", line, column));
+                lx.diagnostics.push(lex_error(codes::UNCLOSED_COMMENT, comment_start, cursor.pos.offset));
             }
             continue;
         }
 
         // =========================
-        // Literales de cadena
+        // Literales de cadena: normales, crudas y bloques de texto
         // =========================
         if ch == '"' {
-            flush_current(&mut current, &mut tokens, line, start_column);
-            let mut string_value = String::new();
-            chars.next(); // consume '"'
-            column += 1;
-            let mut closed = false;
-
-            while let Some(c) = chars.next() {
-                column += 1;
-                if c == '\\' {
-                    // Manejo de secuencias de escape
-                    if let Some(escaped) = chars.next() {
-                        column += 1;
-                        match escaped {
-                            'n' => string_value.push('\n'),
-                            't' => string_value.push('\t'),
-                            '\\' => string_value.push('\\'),
-                            '"' => string_value.push('"'),
-                            _ => return Err(LemonError::new("Invalid escape character", line, column)),
-                        }
-                    }
-                } else if c == '"' {
-                    closed = true;
-                    break;
-                } else {
-                    string_value.push(c);
-                }
-            }
-
-            if !closed {
-                return Err(LemonError::new("Unclosed string literal", line, column));
+            // `r` pegada a las comillas marca una cadena cruda: `r"\d+"`
+            let raw_start = word.filter(|start| &cursor.code[start.offset..cursor.pos.offset] == "r");
+            if raw_start.is_some() {
+                word = None;
+            } else {
+                flush_word(&mut word, cursor, lx, tokens);
             }
-
-            tokens.push(Token {
-                token_type: TokenType::StringLiteral,
-                value: string_value,
-                line,
-                column: start_column,
-            });
-
+            let string_start = raw_start.unwrap_or(cursor.pos);
+            lex_string(cursor, string_start, raw_start.is_some(), lx, tokens);
             continue;
         }
 
         // =========================
-        // Lógicos dobles: &&, ||
+        // Números: 42, 0xFF, 0b1010, 0o17, 1_000, 6.02e23
         // =========================
-        if let Some(double_log) = try_double_char_logical(&mut chars) {
-            flush_current(&mut current, &mut tokens, line, start_column);
-            tokens.push(Token {
-                token_type: TokenType::Logical(double_log),
-                value: double_log.to_string(),
-                line,
-                column,
-            });
-            column += 2;
+        // Un dígito dentro de un identificador (`x1`) sigue siendo parte del identificador
+        if ch.is_ascii_digit() && word.is_none() {
+            let token = lex_number(cursor, lx);
+            tokens.push(token);
             continue;
         }
 
         // =========================
-        // Operador lógico unitario: !
+        // Identificadores o palabras clave (UAX #31)
         // =========================
-        if let Some(logical) = crate::grammar::logicals::Logical::from_str(&ch.to_string()) {
-            flush_current(&mut current, &mut tokens, line, start_column);
-            tokens.push(Token {
-                token_type: TokenType::Logical(logical),
-                value: ch.to_string(),
-                line,
-                column,
-            });
-            chars.next();
-            column += 1;
+        if is_identifier_continue(ch) {
+            word.get_or_insert(cursor.pos);
+            cursor.bump();
+            cursor.bump_ascii_while(|b| b.is_ascii_alphanumeric() || b == b'_');
             continue;
         }
+        flush_word(&mut word, cursor, lx, tokens);
 
         // =========================
-        // Operadores dobles (==, +=, etc.)
+        // Espacios y tabulaciones
         // =========================
-        if let Some(double_op) = try_double_char_operator(&mut chars) {
-            flush_current(&mut current, &mut tokens, line, start_column);
-            tokens.push(Token {
-                token_type: TokenType::Operator(double_op.clone()),
-                value: double_op.to_string(),
-                line,
-                column,
-            });
-            column += 2;
-            continue;
+        let op_start = cursor.pos;
+        if ch == ' ' || ch == '\t' {
+            cursor.bump_ascii_while(|b| b == b' ' || b == b'\t');
         }
 
         // =========================
         // Fin de línea
         // =========================
-        if ch == '\n' {
-            flush_current(&mut current, &mut tokens, line, start_column);
-            chars.next();
-            line += 1;
-            column = 1;
+        else if ch == '\n' || (ch == '\r' && cursor.peek_second() == Some('\n')) {
+            while cursor.bump() != Some('\n') {}
+            if nesting == 0 && (ends_statement(tokens.last().map(|t| t.token_type).or(lx.last)) || ends_with_type(tokens)) {
+                tokens.push(make_token(TokenType::Newline, Lexeme::from_static("\n"), op_start, cursor.pos.offset));
+            }
         }
 
         // =========================
-        // Espacios y tabulaciones
+        // Puntuación: operadores, comparadores, lógicos y símbolos
         // =========================
-        else if ch.is_whitespace() {
-            flush_current(&mut current, &mut tokens, line, start_column);
-            chars.next();
-            column += 1;
+        // Gana la coincidencia más larga, así `..=` no se parte en `..` y `=`
+        else if let Some(&(text, token_type)) = match_punctuation(cursor.rest()) {
+            cursor.bump_ascii(text.len());
+            match token_type {
+                TokenType::Symbol(Symbol::OpenParen | Symbol::OpenBraket) => nesting += 1,
+                TokenType::Symbol(Symbol::CloseParen | Symbol::CloseBraket) => nesting = nesting.saturating_sub(1),
+                _ => {}
+            }
+            tokens.push(make_token(token_type, Lexeme::from_static(text), op_start, cursor.pos.offset));
         }
 
         // =========================
-        // Símbolos simples: ( ) { } , ; ...
+        // Otros espacios (unicode)
         // =========================
-        else if let Some(symbol) = crate::grammar::symbols::Symbol::from_char(ch) {
-            flush_current(&mut current, &mut tokens, line, start_column);
-            tokens.push(Token {
-                token_type: TokenType::Symbol(symbol.clone()),
-                value: symbol.to_char().to_string(),
-                line,
-                column,
-            });
-            chars.next();
-            column += 1;
+        else if ch.is_whitespace() {
+            cursor.bump();
         }
 
         // =========================
-        // Caracteres inválidos
+        // Otros caracteres ASCII visibles: @ $ ` ~ ...
         // =========================
-        else if ch.is_control() {
-            return Err(LemonError::new("Illegal control character", line, column));
-        } else if !ch.is_ascii_graphic() && !ch.is_whitespace() {
-            return Err(LemonError::new("Illegal character in input", line, column));
+        else if ch.is_ascii_graphic() {
+            // Se clasifica solo (como token desconocido), así `a@1` no queda pegado en una sola palabra
+            cursor.bump();
+            let token = classify(cursor, op_start, lx);
+            tokens.push(token);
         }
 
         // =========================
-        // Identificadores o palabras clave
+        // Caracteres inválidos
         // =========================
         else {
-            if current.is_empty() {
-                start_column = column;
-            }
-            current.push(ch);
-            chars.next();
-            column += 1;
+            let code = if ch.is_control() { codes::ILLEGAL_CONTROL_CHARACTER } else { codes::ILLEGAL_CHARACTER };
+            cursor.bump();
+            lx.diagnostics.push(lex_error(code, op_start, cursor.pos.offset));
+            tokens.push(make_token(TokenType::Unknown, Lexeme::from(ch.to_string()), op_start, cursor.pos.offset));
         }
     }
 
     // Procesar la última palabra acumulada (si hay)
-    flush_current(&mut current, &mut tokens, line, start_column);
+    flush_word(&mut word, cursor, lx, tokens);
+    lx.nesting = nesting;
+}
 
-    // Agregar token de fin de archivo
-    tokens.push(Token {
-        token_type: TokenType::EOF,
-        value: "<EOF>".to_string(),
-        line,
-        column,
+/// Indica si un salto de línea después de `last` termina la instrucción.
+///
+/// La línea continúa si termina en un operador binario, una asignación (`=`,
+/// `+=`, ...), `,`, `.`, `?.`, `..`, `?`, `??`, `:` o una flecha, porque la
+/// expresión todavía está incompleta:
+///
+/// ```text
+/// let total = precio +
+///     impuesto
+/// ```
+///
+/// Tampoco se emite un salto al inicio del archivo, después de otro salto ni
+/// después de `;`. Dentro de `(` y `[` los saltos nunca cuentan (lo decide
+/// `lex_range`).
+fn ends_statement(last: Option<TokenType>) -> bool {
+    let Some(last) = last else { return false };
+    !matches!(
+        last,
+        TokenType::Newline
+            | TokenType::Operator(_)
+            | TokenType::Comparator(_)
+            | TokenType::Logical(_)
+            | TokenType::Symbol(
+                Symbol::Define
+                    | Symbol::AddAssign
+                    | Symbol::SubtractAssign
+                    | Symbol::MultiplyAssign
+                    | Symbol::DivideAssign
+                    | Symbol::Comma
+                    | Symbol::Dot
+                    | Symbol::SafeDot
+                    | Symbol::Range
+                    | Symbol::RangeInclusive
+                    | Symbol::Question
+                    | Symbol::Coalesce
+                    | Symbol::Colon
+                    | Symbol::Arrow
+                    | Symbol::FatArrow
+                    | Symbol::Semicolon
+            )
+    )
+}
+
+/// Indica si la línea en curso (los tokens después del último salto o `;`)
+/// termina con la anotación de tipo de un `let` o con el tipo devuelto de
+/// una función: `let x: list<int>`, `let x: int?`, `fn f() -> int?`. Ese
+/// `>` o `?` final cierra el tipo, no continúa una expresión, así que el
+/// salto de línea termina la instrucción.
+fn ends_with_type(tokens: &[Token]) -> bool {
+    let line = tokens
+        .iter()
+        .rposition(|t| matches!(t.token_type, TokenType::Newline | TokenType::Symbol(Symbol::Semicolon)))
+        .map_or(tokens, |i| &tokens[i + 1..]);
+    // Después de `let nombre:` o de `) ->` empieza un tipo
+    let annotation = (0..line.len()).rev().find(|&i| match line[i].token_type {
+        TokenType::Symbol(Symbol::Colon) => {
+            i >= 2
+                && line[i - 1].token_type == TokenType::Identifier
+                && line[i - 2].token_type == TokenType::Keyword(Keyword::Let)
+        }
+        TokenType::Symbol(Symbol::Arrow) => i >= 1 && line[i - 1].token_type == TokenType::Symbol(Symbol::CloseParen),
+        _ => false,
     });
+    annotation.is_some_and(|i| {
+        let ty = &line[i + 1..];
+        type_len(ty) == Some(ty.len())
+    })
+}
 
-    Ok(tokens)
+/// Cantidad de tokens del tipo con que empieza `tokens` (`int`, `Punto`,
+/// `map<string, list<int>>`, `int?`), o `None` si no empieza con un tipo.
+/// Sigue la gramática de `Parser::parse_type`.
+fn type_len(tokens: &[Token]) -> Option<usize> {
+    let mut len = match tokens.first()?.token_type {
+        TokenType::Type(_) | TokenType::Identifier => 1,
+        _ => return None,
+    };
+    if tokens.get(len).map(|t| t.token_type) == Some(TokenType::Comparator(Comparator::Less)) {
+        len += 1;
+        loop {
+            len += type_len(&tokens[len..])?;
+            match tokens.get(len)?.token_type {
+                TokenType::Symbol(Symbol::Comma) => len += 1,
+                TokenType::Comparator(Comparator::Greater) => {
+                    len += 1;
+                    break;
+                }
+                _ => return None,
+            }
+        }
+    }
+    if tokens.get(len).map(|t| t.token_type) == Some(TokenType::Symbol(Symbol::Question)) {
+        len += 1;
+    }
+    Some(len)
 }
 
-/// Si hay texto acumulado, clasifícalo como un token y agrégalo al vector
-fn flush_current(current: &mut String, tokens: &mut Vec<Token>, line: usize, column: usize) {
-    if current.is_empty() {
+/// Lee una cadena cuyas comillas empiezan en la posición actual; `start` es
+/// el inicio del token (la `r` en las cadenas crudas).
+///
+/// Formas aceptadas:
+/// - `"texto"`: con escapes e interpolaciones.
+/// - `r"C:\ruta"`: cruda, sin escapes ni interpolaciones.
+/// - `"""` ... `"""`: bloque de varias líneas (ver [`text_block_layout`]);
+///   también existe en forma cruda con `r"""`.
+///
+/// Sin interpolaciones se emite un único `StringLiteral` con el texto ya
+/// procesado (escapes aplicados, `{{`/`}}` convertidos en `{`/`}`). Con
+/// interpolaciones (`"Hola, {nombre}"`) se emite:
+///
+/// `InterpolationStart`, y luego fragmentos `StringFragment` alternados con
+/// `{` + los tokens de la expresión + `}`, y por último `InterpolationEnd`.
+fn lex_string(cursor: &mut Cursor, start: Position, raw: bool, lx: &mut Lexing, tokens: &mut Vec<Token>) {
+    let triple = cursor.peek_second() == Some('"') && cursor.peek_nth(2) == Some('"');
+    let delimiter = if triple { 3 } else { 1 };
+    for _ in 0..delimiter {
+        cursor.bump(); // consume las comillas de apertura
+    }
+    let mut fragment_start = cursor.pos;    // Los fragmentos cubren todo el texto, sangría incluida
+
+    // En los bloques el cierre y la sangría se conocen de antemano
+    let block = if triple {
+        // El cierre se busca solo dentro del rango: dentro de una interpolación, antes de su `}`
+        match text_block_layout(&cursor.code[..cursor.end], cursor.pos.offset, raw) {
+            Some(layout) => Some(layout),
+            None => {
+                while cursor.bump().is_some() {}
+                let error = Diagnostic::error(codes::UNCLOSED_STRING, &tr("E0102.block", &[]))
+                    .with_span(Span::new(start.offset, cursor.pos.offset));
+                lx.diagnostics.push(error);
+                let text = Lexeme::from(&cursor.code[start.offset..cursor.pos.offset]);
+                tokens.push(make_token(TokenType::Unknown, text, start, cursor.pos.offset));
+                return;
+            }
+        }
+    } else {
+        None
+    };
+    if let Some(layout) = &block {
+        while cursor.pos.offset < layout.content_start {
+            cursor.bump();
+        }
+        if layout.strip_first_line {
+            skip_indent(cursor, layout.indent, layout.content_end);
+        }
+    }
+
+    let mut parts = Vec::new();             // Fragmentos y tokens de las interpolaciones
+    let mut interpolated = false;
+    let mut fragment = String::new();       // Texto del fragmento actual
+    let mut closing_quote = None;
+
+    loop {
+        let char_start = cursor.pos;
+        if let Some(layout) = &block
+            && char_start.offset >= layout.content_end
+        {
+            // El salto de línea anterior al cierre no es parte del texto
+            while cursor.pos.offset < layout.close {
+                cursor.bump();
+            }
+            closing_quote = Some(cursor.pos);
+            break;
+        }
+        let Some(c) = cursor.bump() else { break };
+        match c {
+            '\\' if !raw => lex_escape(cursor, char_start, &mut fragment, &mut lx.diagnostics),
+            '"' if block.is_none() => {
+                closing_quote = Some(char_start);
+                break;
+            }
+            '\n' if block.is_some() => {
+                fragment.push('\n');
+                if let Some(layout) = &block {
+                    skip_indent(cursor, layout.indent, layout.content_end);
+                }
+            }
+
+            // `{{` y `}}` son llaves literales
+            '{' | '}' if !raw && cursor.peek() == Some(c) => {
+                cursor.bump();
+                fragment.push(c);
+            }
+
+            // En un bloque, la interpolación tiene que cerrarse antes de las comillas de cierre
+            '{' if !raw => match find_interpolation_end(cursor.code, cursor.pos.offset)
+                .filter(|&close| block.as_ref().is_none_or(|layout| close < layout.content_end))
+            {
+                Some(close) => {
+                    interpolated = true;
+                    push_fragment(&mut parts, &mut fragment, fragment_start, char_start.offset);
+                    parts.push(make_token(TokenType::Symbol(Symbol::OpenBrace), Lexeme::from_static("{"), char_start, cursor.pos.offset));
+
+                    // La expresión se analiza como código normal, con posiciones absolutas
+                    // Sus paréntesis no se mezclan con los de afuera
+                    let mut inner = Cursor::at(cursor.code, cursor.pos, close);
+                    let nesting = std::mem::take(&mut lx.nesting);
+                    lex_range(&mut inner, lx, &mut parts);
+                    lx.nesting = nesting;
+                    while cursor.pos.offset < close {
+                        cursor.bump();
+                    }
+
+                    let close_start = cursor.pos;
+                    cursor.bump(); // consume '}'
+                    parts.push(make_token(TokenType::Symbol(Symbol::CloseBrace), Lexeme::from_static("}"), close_start, cursor.pos.offset));
+                    fragment_start = cursor.pos;
+                }
+                None => {
+                    // Sin `}` de cierre: se informa y la llave queda como texto
+                    lx.diagnostics.push(interpolation_error("E0109.unclosed", char_start, cursor.pos.offset));
+                    fragment.push('{');
+                }
+            },
+            '}' if !raw => {
+                lx.diagnostics.push(interpolation_error("E0109.stray_close", char_start, cursor.pos.offset));
+                fragment.push('}');
+            }
+
+            _ => fragment.push(c),
+        }
+    }
+
+    let Some(closing_quote) = closing_quote else {
+        // La cadena se comió el resto del archivo: queda como un único token desconocido
+        lx.diagnostics.push(lex_error(codes::UNCLOSED_STRING, start, cursor.pos.offset));
+        let text = Lexeme::from(&cursor.code[start.offset..cursor.pos.offset]);
+        tokens.push(make_token(TokenType::Unknown, text, start, cursor.pos.offset));
         return;
+    };
+    if block.is_some() {
+        for _ in 0..delimiter {
+            cursor.bump(); // consume las comillas de cierre
+        }
     }
-    tokens.push(classify(current, line, column));
-    current.clear();
+
+    if !interpolated {
+        tokens.push(make_token(TokenType::StringLiteral, Lexeme::from(fragment), start, cursor.pos.offset));
+        return;
+    }
+
+    push_fragment(&mut parts, &mut fragment, fragment_start, closing_quote.offset);
+    let opening = &cursor.code[start.offset..start.offset + delimiter];
+    tokens.push(make_token(TokenType::InterpolationStart, Lexeme::from(opening), start, start.offset + delimiter));
+    tokens.extend(parts);
+    let closing = &cursor.code[closing_quote.offset..cursor.pos.offset];
+    tokens.push(make_token(TokenType::InterpolationEnd, Lexeme::from(closing), closing_quote, cursor.pos.offset));
 }
 
-/// Clasifica una palabra como keyword, tipo, operador, identificador, número, etc.
-fn classify(word: &str, line: usize, column: usize) -> Token {
-    use TokenType::*;
-    let value = word.to_string();
+/// Procesa una secuencia de escape; la `\` en `start` ya fue consumida.
+///
+/// Escapes válidos: `\n`, `\t`, `\r`, `\0`, `\\`, `\"`, `\xNN` (ASCII,
+/// hasta `\x7F`) y `\u{...}` (de 1 a 6 dígitos hexadecimales). Un escape
+/// inválido se informa y su texto se conserva tal cual, sin invalidar la cadena.
+fn lex_escape(cursor: &mut Cursor, start: Position, out: &mut String, diagnostics: &mut Vec<Diagnostic>) {
+    let Some(escaped) = cursor.bump() else { return };
+    let value = match escaped {
+        'n' => Ok('\n'),
+        't' => Ok('\t'),
+        'r' => Ok('\r'),
+        '0' => Ok('\0'),
+        '\\' => Ok('\\'),
+        '"' => Ok('"'),
+        'x' => {
+            let mut digits = String::new();
+            while digits.len() < 2 && cursor.peek().is_some_and(|c| c.is_ascii_hexdigit()) {
+                digits.extend(cursor.bump());
+            }
+            match u8::from_str_radix(&digits, 16) {
+                Ok(byte) if digits.len() == 2 && byte.is_ascii() => Ok(byte as char),
+                _ => Err(tr("E0103.hex", &[])),
+            }
+        }
+        'u' => {
+            let mut digits = String::new();
+            let braced = cursor.peek() == Some('{');
+            if braced {
+                cursor.bump();
+                while cursor.peek().is_some_and(|c| c.is_ascii_hexdigit()) {
+                    digits.extend(cursor.bump());
+                }
+            }
+            let closed = braced && cursor.peek() == Some('}');
+            if closed {
+                cursor.bump();
+            }
+            if !closed || digits.is_empty() || digits.len() > 6 {
+                Err(tr("E0103.unicode", &[]))
+            } else {
+                // Los sustitutos (`D800`-`DFFF`) y los valores mayores a `10FFFF` no son caracteres
+                u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| tr("E0103.scalar", &[&digits]))
+            }
+        }
+        _ => Err(tr(codes::INVALID_ESCAPE, &[])),
+    };
+
+    match value {
+        Ok(c) => out.push(c),
+        Err(message) => {
+            let error = Diagnostic::error(codes::INVALID_ESCAPE, &message)
+                .with_span(Span::new(start.offset, cursor.pos.offset))
+                .with_help(&tr("E0103.help", &[]));
+            diagnostics.push(error);
+            out.push_str(&cursor.code[start.offset..cursor.pos.offset]);
+        }
+    }
+}
 
-    if let Ok(_) = word.replace(",", ".").parse::<f64>() {
-        return Token { token_type: Number, value, line, column };
+/// Forma de un bloque `"""`, calculada antes de leer su contenido.
+struct TextBlockLayout {
+    /// Byte donde empieza el texto (después de la primera línea si está vacía).
+    content_start: usize,
+    /// Byte donde termina el texto (antes del último salto si la línea del cierre está vacía).
+    content_end: usize,
+    /// Byte de las comillas de cierre.
+    close: usize,
+    /// Espacios que se quitan al inicio de cada línea.
+    indent: usize,
+    /// Si la primera línea del texto también lleva sangría que quitar.
+    strip_first_line: bool,
+}
+
+/// Ubica el cierre de un bloque `"""` cuyo contenido empieza en el byte
+/// `from` y calcula la sangría común.
+///
+/// Si la línea de apertura y la del cierre solo tienen espacios, no forman
+/// parte del texto. La sangría que se quita es la menor entre las líneas no
+/// vacías y la línea del cierre, así que el texto se puede alinear con el
+/// código que lo rodea:
+///
+/// ```text
+///     let poema = """
+///         Caminante, no hay camino,
+///           se hace camino al andar.
+///         """
+/// ```
+///
+/// produce `"Caminante, no hay camino,\n  se hace camino al andar."`.
+fn text_block_layout(code: &str, from: usize, raw: bool) -> Option<TextBlockLayout> {
+    let bytes = code.as_bytes();
+    let mut close = from;
+    loop {
+        match bytes.get(close)? {
+            b'\\' if !raw => close += 2,
+            b'"' if code[close..].starts_with("\"\"\"") => break,
+            _ => close += 1,
+        }
     }
 
-    if let Some(kw) = crate::grammar::keywords::Keyword::from_str(word) {
-        return Token { token_type: Keyword(kw), value, line, column };
+    let content = &code[from..close];
+    let is_blank = |line: &str| line.trim_matches([' ', '\t', '\r']).is_empty();
+    let indent_of = |line: &str| line.len() - line.trim_start_matches([' ', '\t']).len();
+
+    let mut content_start = from;
+    let mut content_end = close;
+    let mut indent = usize::MAX;
+    let mut lines: Vec<&str> = content.split('\n').collect();
+
+    // Primera línea vacía: el texto empieza en la siguiente
+    let strip_first_line = lines.len() > 1 && is_blank(lines[0]);
+    if strip_first_line {
+        content_start += lines[0].len() + 1;
+        lines.remove(0);
+    } else if lines.len() > 1 {
+        lines.remove(0);
+    } else {
+        // Bloque de una sola línea: no hay sangría que quitar
+        return Some(TextBlockLayout { content_start, content_end, close, indent: 0, strip_first_line: false });
     }
 
-    if let Some(ty) = crate::grammar::types::Type::from_str(word) {
-        return Token { token_type: Type(ty), value, line, column };
+    // Línea del cierre vacía: su sangría cuenta y su salto de línea no es texto
+    if let Some(last) = lines.last()
+        && is_blank(last)
+    {
+        indent = indent_of(last);
+        content_end = close - last.len() - 1;
+        lines.pop();
     }
+    for line in lines.iter().filter(|line| !is_blank(line)) {
+        indent = indent.min(indent_of(line));
+    }
+
+    let indent = if indent == usize::MAX { 0 } else { indent };
+    Some(TextBlockLayout { content_start: content_start.min(content_end), content_end, close, indent, strip_first_line })
+}
 
-    if let Some(op) = crate::grammar::operators::Operator::from_str(word) {
-        return Token { token_type: Operator(op), value, line, column };
+/// Consume hasta `indent` espacios o tabulaciones al inicio de una línea de un bloque.
+fn skip_indent(cursor: &mut Cursor, indent: usize, end: usize) {
+    for _ in 0..indent {
+        if cursor.pos.offset < end && matches!(cursor.peek(), Some(' ' | '\t')) {
+            cursor.bump();
+        } else {
+            break;
+        }
     }
+}
+
+/// Agrega el fragmento de texto acumulado como `StringFragment`. Se omite
+/// solo si no cubre nada del código fuente: uno que quedó vacío tras quitar
+/// la sangría se conserva para que el texto original siga completo.
+fn push_fragment(parts: &mut Vec<Token>, fragment: &mut String, start: Position, end: usize) {
+    if end > start.offset {
+        parts.push(make_token(TokenType::StringFragment, Lexeme::from(std::mem::take(fragment)), start, end));
+    }
+}
+
+/// Error de interpolación con el mensaje `key` del catálogo.
+fn interpolation_error(key: &str, start: Position, end: usize) -> Diagnostic {
+    Diagnostic::error(codes::INVALID_INTERPOLATION, &tr(key, &[])).with_span(Span::new(start.offset, end))
+}
+
+/// Busca el `}` que cierra una interpolación cuyo contenido empieza en el
+/// byte `from`. Respeta llaves anidadas y cadenas dentro de la expresión
+/// (`"{f("a")}"`). La expresión debe terminar en la misma línea.
+fn find_interpolation_end(code: &str, from: usize) -> Option<usize> {
+    let bytes = code.as_bytes();
+    let mut depth = 0;
+    let mut i = from;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' => depth += 1,
+            b'}' if depth == 0 => return Some(i),
+            b'}' => depth -= 1,
+            b'"' => {
+                i = skip_string(code, i + 1)?;
+                continue;
+            }
+            b'\n' => return None,
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Salta una cadena anidada cuyo contenido empieza en el byte `from` y
+/// devuelve la posición después de su `"` de cierre. Las interpolaciones
+/// internas se saltan completas, así que `"{"{x}"}"` se reconoce bien.
+/// Como la expresión exterior es de una sola línea, la cadena también.
+fn skip_string(code: &str, from: usize) -> Option<usize> {
+    let bytes = code.as_bytes();
+    let mut i = from;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 1,
+            b'"' => return Some(i + 1),
+            b'{' | b'}' if bytes.get(i + 1) == Some(&bytes[i]) => i += 1,
+            b'{' => i = find_interpolation_end(code, i + 1)?,
+            b'\n' => return None,
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Lee un literal numérico que empieza en la posición actual del cursor.
+///
+/// Acepta enteros decimales, hexadecimales (`0xFF`), binarios (`0b1010`) y
+/// octales (`0o17`), decimales con parte fraccionaria y/o exponente
+/// (`6.02e23`, `1e-3`) y `_` como separador (`1_000_000`). El valor del
+/// token es el texto tal como está escrito; ver `integer_value` y `float_value`.
+///
+/// Si el número sigue con letras o dígitos que no le corresponden (`12abc`,
+/// `0b102`) o no cabe en su tipo, todo el texto queda como un token `Unknown`
+/// junto con su diagnóstico.
+fn lex_number(cursor: &mut Cursor, lx: &mut Lexing) -> Token {
+    let start = cursor.pos;
+    let radix = match (cursor.peek(), cursor.peek_second()) {
+        (Some('0'), Some('x' | 'X')) => 16,
+        (Some('0'), Some('b' | 'B')) => 2,
+        (Some('0'), Some('o' | 'O')) => 8,
+        _ => 10,
+    };
+
+    let mut is_float = false;
+    let has_digits = if radix == 10 {
+        eat_digits(cursor, 10);
+        // Solo es decimal si después del punto hay un dígito (`1.5`, no `obj.1`)
+        if cursor.peek() == Some('.') && cursor.peek_second().is_some_and(|c| c.is_ascii_digit()) {
+            cursor.bump(); // consume '.'
+            eat_digits(cursor, 10);
+            is_float = true;
+        }
+        // Exponente: `e5`, `E+5`, `e-5`
+        if matches!(cursor.peek(), Some('e' | 'E')) {
+            let sign = usize::from(matches!(cursor.peek_second(), Some('+' | '-')));
+            if cursor.peek_nth(1 + sign).is_some_and(|c| c.is_ascii_digit()) {
+                for _ in 0..=sign {
+                    cursor.bump(); // consume 'e' y el signo
+                }
+                eat_digits(cursor, 10);
+                is_float = true;
+            }
+        }
+        true
+    } else {
+        cursor.bump(); // consume '0'
+        cursor.bump(); // consume 'x', 'b' u 'o'
+        eat_digits(cursor, radix)
+    };
 
-    if let Some(cmp) = crate::grammar::comparators::Comparator::from_str(word) {
-        return Token { token_type: Comparator(cmp), value, line, column };
+    // Letras o dígitos pegados al número lo vuelven inválido
+    let mut malformed = !has_digits;
+    while cursor.peek().is_some_and(is_identifier_continue) {
+        malformed = true;
+        cursor.bump();
     }
 
-    if let Some(log) = crate::grammar::logicals::Logical::from_str(word) {
-        return Token { token_type: Logical(log), value, line, column };
+    let end = cursor.pos.offset;
+    let text = &cursor.code[start.offset..end];
+    let (token_type, error) = if malformed {
+        let mut error = Diagnostic::error(codes::MALFORMED_NUMBER, &tr(codes::MALFORMED_NUMBER, &[&text]))
+            .with_span(Span::new(start.offset, end));
+        if radix == 10 && !is_float {
+            error = error.with_help(&tr("E0107.identifier", &[]));
+        }
+        (TokenType::Unknown, Some(error))
+    } else if is_float {
+        match float_value(text) {
+            Some(_) => (TokenType::Float, None),
+            None => (TokenType::Unknown, Some(out_of_range(text, start, end))),
+        }
+    } else {
+        match integer_value(text) {
+            Some(_) => (TokenType::Integer, None),
+            None => (TokenType::Unknown, Some(out_of_range(text, start, end))),
+        }
+    };
+    lx.diagnostics.extend(error);
+    make_token(token_type, lx.interner.intern(text), start, end)
+}
+
+/// Consume dígitos de la base `radix` y separadores `_`.
+/// Indica si se consumió al menos un dígito.
+fn eat_digits(cursor: &mut Cursor, radix: u32) -> bool {
+    let mut any = false;
+    while let Some(c) = cursor.peek() {
+        if c.is_digit(radix) {
+            any = true;
+        } else if c != '_' {
+            break;
+        }
+        cursor.bump();
     }
+    any
+}
 
-    if let Some(comment) = crate::grammar::comments::Comment::from_str(word) {
-        return Token { token_type: Comment(comment), value, line, column };
+/// Error de número que no cabe en su tipo (`i64` o `f64` finito).
+fn out_of_range(text: &str, start: Position, end: usize) -> Diagnostic {
+    Diagnostic::error(codes::NUMBER_OUT_OF_RANGE, &tr(codes::NUMBER_OUT_OF_RANGE, &[&text]))
+        .with_span(Span::new(start.offset, end))
+        .with_note(&tr("E0108.note", &[]))
+}
+
+/// Valor de un literal entero tal como lo emite el lexer (`42`, `0xFF`,
+/// `0b1010`, `0o17`, `1_000`). `None` si no es válido o no cabe en un `i64`.
+///
+/// ```
+/// # use lemon_core::lexer::integer_value;
+/// assert_eq!(integer_value("0xFF"), Some(255));
+/// assert_eq!(integer_value("1_000_000"), Some(1_000_000));
+/// assert_eq!(integer_value("99999999999999999999"), None);
+/// ```
+pub fn integer_value(text: &str) -> Option<i64> {
+    let digits = without_separators(text);
+    let (radix, digits) = match digits.get(..2) {
+        Some("0x" | "0X") => (16, &digits[2..]),
+        Some("0b" | "0B") => (2, &digits[2..]),
+        Some("0o" | "0O") => (8, &digits[2..]),
+        _ => (10, &digits[..]),
+    };
+    i64::from_str_radix(digits, radix).ok()
+}
+
+/// Valor de un literal decimal tal como lo emite el lexer (`3.14`, `6.02e23`).
+/// `None` si no es válido o es demasiado grande para un `f64`.
+pub fn float_value(text: &str) -> Option<f64> {
+    without_separators(text).parse::<f64>().ok().filter(|value| value.is_finite())
+}
+
+/// `text` sin los separadores `_`; solo copia el texto si tiene alguno.
+fn without_separators(text: &str) -> std::borrow::Cow<'_, str> {
+    if text.contains('_') { text.replace('_', "").into() } else { text.into() }
+}
+
+/// Si hay una palabra en curso (que empezó en `word`), clasifícala y agrega
+/// su token.
+fn flush_word(word: &mut Option<Position>, cursor: &Cursor, lx: &mut Lexing, tokens: &mut Vec<Token>) {
+    if let Some(start) = word.take() {
+        tokens.push(classify(cursor, start, lx));
     }
+}
+
+/// Clasifica el texto entre `start` y la posición del cursor como keyword,
+/// tipo, operador, identificador, etc. Una palabra que no se reconoce se
+/// emite como `Unknown` junto con su diagnóstico.
+fn classify(cursor: &Cursor, start: Position, lx: &mut Lexing) -> Token {
+    let end = cursor.pos.offset;
+    let word = &cursor.code[start.offset..end];
+    let token_type = word_type(word, lx.keywords);
 
-    // Número mal formado (empieza con dígito pero tiene basura)
-    if word.starts_with(|c: char| c.is_ascii_digit()) {
-        return Token { token_type: Unknown, value, line, column };
+    // Identificador válido: se guarda en NFC, así `año` escrito con `ñ`
+    // precompuesta o con `n` + tilde combinante es el mismo nombre
+    let value = if token_type == TokenType::Identifier && !word.is_ascii() {
+        lx.interner.intern(&word.nfc().collect::<String>())
+    } else {
+        lx.interner.intern(word)
+    };
+    if token_type == TokenType::Unknown {
+        lx.diagnostics.push(lex_error(codes::UNKNOWN_TOKEN, start, end));
     }
+    make_token(token_type, value, start, end)
+}
 
-    // Identificador válido
-    if word.chars().all(|c| c.is_alphanumeric() || c == '_') {
-        return Token { token_type: Identifier, value, line, column };
+/// Tipo de token de una palabra (o de un carácter ASCII suelto que no es
+/// puntuación). `keywords` es el idioma de las palabras clave del archivo.
+fn word_type(word: &str, keywords: Locale) -> TokenType {
+    use TokenType::*;
+
+    // Las palabras contextuales (`box`, `window`, ...) se entregan como
+    // identificadores; el parser las reconoce por su posición.
+    if let Some(kw) = crate::grammar::keywords::Keyword::parse_in(word, keywords)
+        && !kw.is_contextual()
+    {
+        return Keyword(kw);
+    }
+    if let Some(ty) = crate::grammar::types::Type::parse(word) {
+        return Type(ty);
+    }
+    // Los operadores simbólicos no llegan aquí (ver `match_punctuation`),
+    // pero los lógicos también se escriben con palabras: `and`, `or`, `not`
+    if let Some(log) = crate::grammar::logicals::Logical::from_word(word) {
+        return Logical(log);
+    }
+    if is_identifier(word) {
+        return Identifier;
     }
+    Unknown
+}
 
-    // Token desconocido
-    Token { token_type: Unknown, value, line, column }
+/// Carácter que puede empezar un identificador: `XID_Start` o `_`.
+fn is_identifier_start(c: char) -> bool {
+    if c.is_ascii() {
+        return c.is_ascii_alphabetic() || c == '_';
+    }
+    c.is_xid_start()
 }
 
-/// Detecta operadores de dos caracteres (==, +=, <=, etc.)
-fn try_double_char_operator(
-    chars: &mut std::iter::Peekable<std::str::Chars>,
-) -> Option<crate::grammar::operators::Operator> {
-    let ch1 = chars.peek().copied()?;
-    let ch2 = chars.clone().nth(1)?;
-    let pair = format!("{}{}", ch1, ch2);
+/// Carácter que puede seguir dentro de un identificador: `XID_Continue`
+/// (letras, dígitos, marcas combinantes y `_`).
+fn is_identifier_continue(c: char) -> bool {
+    if c.is_ascii() {
+        return c.is_ascii_alphanumeric() || c == '_';
+    }
+    c.is_xid_continue()
+}
 
-    crate::grammar::operators::Operator::from_str(&pair).map(|op| {
-        chars.next(); // consume ch1
-        chars.next(); // consume ch2
-        op
-    })
+/// Indica si `word` es un identificador según UAX #31 (`año`, `función`, `_x1`).
+pub fn is_identifier(word: &str) -> bool {
+    let mut chars = word.chars();
+    chars.next().is_some_and(is_identifier_start) && chars.all(is_identifier_continue)
 }
 
-/// Detecta operadores lógicos dobles: &&, ||
-fn try_double_char_logical(
-    chars: &mut std::iter::Peekable<std::str::Chars>,
-) -> Option<crate::grammar::logicals::Logical> {
-    let ch1 = chars.peek().copied()?;
-    let ch2 = chars.clone().nth(1)?;
-    let pair = format!("{}{}", ch1, ch2);
+/// Tabla de puntuación, armada con las tablas de `grammar` (operadores,
+/// comparadores, lógicos simbólicos y símbolos). Está indexada por el primer
+/// byte y cada grupo va de mayor a menor largo: la primera entrada que
+/// coincide es la más larga.
+static PUNCTUATION: LazyLock<Vec<Vec<(&'static str, TokenType)>>> = LazyLock::new(|| {
+    let mut table = vec![Vec::new(); 128];
+    let entries = OPERATORS
+        .iter()
+        .map(|&(text, op)| (text, TokenType::Operator(op)))
+        .chain(COMPARATORS.iter().map(|&(text, cmp)| (text, TokenType::Comparator(cmp))))
+        .chain(LOGICAL_SYMBOLS.iter().map(|&(text, log)| (text, TokenType::Logical(log))))
+        .chain(SYMBOLS.iter().map(|&(text, symbol)| (text, TokenType::Symbol(symbol))));
+    for (text, token_type) in entries {
+        table[usize::from(text.as_bytes()[0])].push((text, token_type));
+    }
+    for group in &mut table {
+        group.sort_by_key(|(text, _): &(&str, TokenType)| std::cmp::Reverse(text.len()));
+    }
+    table
+});
 
-    crate::grammar::logicals::Logical::from_str(&pair).map(|log| {
-        chars.next();
-        chars.next();
-        log
-    })
+/// Puntuación más larga al inicio de `rest`, con su texto.
+fn match_punctuation(rest: &str) -> Option<&'static (&'static str, TokenType)> {
+    let first = *rest.as_bytes().first()?;
+    PUNCTUATION.get(usize::from(first))?.iter().find(|(text, _)| rest.starts_with(text))
 }
@@ -1,34 +1,96 @@
+// Los errores se devuelven como `Diagnostic` por valor: son el camino poco
+// frecuente y simplifica el código frente a devolver `Box<Diagnostic>`.
+#![allow(clippy::result_large_err)]
+
 // === Módulos públicos y privados ===
 
 pub mod lexer;      // Analizador léxico (tokenizer)
-mod parser;         // Analizador sintáctico
-mod ast;            // Árbol de sintaxis abstracta
-mod error;          // Tipos de errores personalizados
-mod eval;           // Evaluador (en desarrollo)
+pub mod parser;     // Analizador sintáctico
+pub mod ast;        // Árbol de sintaxis abstracta
+pub mod diagnostic; // Diagnósticos estructurados (errores y advertencias)
+pub mod resolve;    // Resolución de nombres (ámbitos y declaraciones)
+pub mod check;      // Comprobación de tipos estática
+pub mod eval;       // Evaluador (intérprete de árbol)
 pub mod token;      // Definiciones de tokens
+pub mod span;       // Rangos de bytes y mapa de fuente (línea/columna)
 pub mod grammar;    // Gramática general (keywords, símbolos, tipos, etc.)
+pub mod i18n;       // Catálogo de mensajes en español e inglés
+pub mod format;     // Formateador (conversión de palabras clave entre idiomas)
 
 // === Imports ===
 
-use error::LemonError;
+pub use diagnostic::Diagnostic;
 //use token::Token;
 use ast::statements::Statement;
+use lexer::Lexer;
 use parser::Parser;
+pub use eval::Value;
+use eval::Interpreter;
+use resolve::Resolution;
+pub use i18n::{locale, set_locale, Locale};
 
 // === Punto de entrada ===
 
-/// Interpreta el código fuente completo.
-/// Ejecuta las fases de tokenización, parsing y evaluación.
-pub fn interpret(code: &str) -> Result<Vec<Statement>, LemonError> {
-    // 1. Tokenización
-    let tokens = lexer::tokenize(code)?; // Devuelve Vec<Token> o error léxico
+/// Analiza el código fuente sin ejecutarlo y sin detenerse en errores.
+/// Devuelve el AST (parcial si hubo errores) y todos los diagnósticos
+/// léxicos y de sintaxis, ordenados por posición en el archivo.
+pub fn parse_with_diagnostics(code: &str) -> (Vec<Statement>, Vec<Diagnostic>) {
+    // 1. Tokenización a demanda (los caracteres inválidos quedan como tokens `Unknown`)
+    // 2. Parsing con recuperación de errores
+    let mut parser = Parser::from_lexer(Lexer::new(code));
+    let (statements, mut diagnostics) = parser.parse();
 
-    // 2. Parsing
-    let mut parser = Parser::new(tokens);
-    let ast = parser.parse().map_err(|msg| LemonError::new(&msg, 0, 0))?;
+    diagnostics.sort_by_key(|d| d.span().map_or(usize::MAX, |span| span.start));
+    (statements, diagnostics)
+}
+
+/// Analiza el código fuente sin ejecutarlo.
+/// Ejecuta las fases de tokenización y parsing, y devuelve el AST, o todos
+/// los errores encontrados si hubo al menos uno.
+pub fn parse(code: &str) -> Result<Vec<Statement>, Vec<Diagnostic>> {
+    let (statements, diagnostics) = parse_with_diagnostics(code);
+    if diagnostics.iter().any(Diagnostic::is_error) {
+        return Err(diagnostics);
+    }
+    Ok(statements)
+}
 
-    // 3. (Opcional) Evaluación
-    // let result = eval::evaluate(&ast)?;  // Solo si implementas el evaluador
+/// Analiza el código fuente y comprueba sus nombres y tipos, sin ejecutarlo.
+/// Devuelve el AST, la resolución de nombres (a qué declaración se refiere
+/// cada uso) y los diagnósticos de todas las fases, ordenados por posición.
+/// Nombres y tipos solo se comprueban si no hubo errores de sintaxis; si los
+/// hubo, la resolución queda vacía.
+///
+/// ```
+/// let (statements, resolution, diagnostics) = lemon_core::check_with_diagnostics("let x = 1\ndel x\n");
+/// assert!(diagnostics.is_empty());
+/// assert_eq!(resolution.resolved(statements[1].id), resolution.declared(statements[0].id));
+/// ```
+pub fn check_with_diagnostics(code: &str) -> (Vec<Statement>, Resolution, Vec<Diagnostic>) {
+    let (statements, mut diagnostics) = parse_with_diagnostics(code);
+    if diagnostics.iter().any(Diagnostic::is_error) {
+        return (statements, Resolution::default(), diagnostics);
+    }
+
+    // 3. Resolución de nombres
+    let resolution = resolve::resolve(&statements);
+    diagnostics.extend(resolution.diagnostics.iter().cloned());
+    // 4. Comprobación de tipos
+    diagnostics.extend(check::check(&statements));
+    diagnostics.sort_by_key(|d| d.span().map_or(usize::MAX, |span| span.start));
+    (statements, resolution, diagnostics)
+}
+
+/// Interpreta el código fuente completo.
+/// Ejecuta las fases de tokenización, parsing, resolución de nombres,
+/// comprobación de tipos y evaluación. Un programa con errores de sintaxis,
+/// de nombres o de tipos no se ejecuta.
+pub fn interpret(code: &str) -> Result<Value, Vec<Diagnostic>> {
+    let (ast, resolution, diagnostics) = check_with_diagnostics(code);
+    if diagnostics.iter().any(Diagnostic::is_error) {
+        return Err(diagnostics);
+    }
 
-    Ok(ast) // Devuelve el AST por ahora
+    // 5. Evaluación
+    Interpreter::new(resolution).run(&ast).map_err(|e| vec![e]) // El resultado del programa o el error en tiempo de ejecución
 }
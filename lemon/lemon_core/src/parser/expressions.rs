@@ -1,278 +1,220 @@
+use crate::ast::{BinaryOp, Expression, ExpressionKind, InterpolationPart};
+use crate::diagnostic::{codes, Diagnostic};
+use crate::grammar::*;
+use crate::i18n::tr;
+use crate::lexer;
+use crate::parser::helpers::{binding_power, token_to_binary_op, token_to_unary_op, UNARY_BINDING_POWER};
+use crate::parser::Parser;
 use crate::token::{Token, TokenType};
-use crate::error::LemonError;
 
-/// Función principal: convierte el código fuente en una lista de tokens
-pub fn tokenize(code: &str) -> Result<Vec<Token>, LemonError> {
-    let mut tokens = Vec::new();
-    let mut current = String::new();
-    let mut chars = code.chars().peekable();
-    let mut line = 1;
-    let mut column = 1;
-    let mut start_column = column;
+/// Punto de entrada externo para parser de expresiones
+pub fn parse_expression_tokens(tokens: &[Token]) -> Result<Expression, Diagnostic> {
+    let mut parser = Parser::new(tokens.to_vec());
+    parser.parse_expression()
+}
 
-    while let Some(&ch) = chars.peek() {
-        // =========================
-        // Comentarios
-        // =========================
-        if ch == '/' && chars.clone().nth(1) == Some('/') {
-            while let Some(&c) = chars.peek() {
-                chars.next();
-                column += 1;
-                if c == '\n' {
-                    break;
-                }
+/// Métodos del parser dedicados a las expresiones.
+///
+/// Precedencia, de menor a mayor:
+/// asignación (`=`, `+=`, ...) → ternario (`? :`) → operadores binarios (ver
+/// `helpers::binding_power`) → unarios (`-`, `!`) → potencia (`^`)
+/// → llamadas y acceso a propiedades → literales y agrupaciones.
+impl Parser {
+    /// Analiza una expresión completa.
+    pub fn parse_expression(&mut self) -> Result<Expression, Diagnostic> {
+        self.parse_assignment()
+    }
+
+    /// `objetivo = valor` (asociativa a la derecha).
+    /// El objetivo puede ser una variable (`x = 1`) o una propiedad (`obj.campo = 1`).
+    /// Las asignaciones compuestas (`+=`, `-=`, `*=`, `/=`) forman un
+    /// `CompoundAssign` con el mismo objetivo.
+    fn parse_assignment(&mut self) -> Result<Expression, Diagnostic> {
+        let target = self.parse_ternary()?;
+
+        let operator = match self.peek().token_type {
+            TokenType::Symbol(Symbol::Define) => None,
+            TokenType::Symbol(Symbol::AddAssign) => Some(BinaryOp::Add),
+            TokenType::Symbol(Symbol::SubtractAssign) => Some(BinaryOp::Sub),
+            TokenType::Symbol(Symbol::MultiplyAssign) => Some(BinaryOp::Mul),
+            TokenType::Symbol(Symbol::DivideAssign) => Some(BinaryOp::Div),
+            _ => return Ok(target),
+        };
+        let error = self.error_at_current(codes::INVALID_ASSIGNMENT_TARGET, &tr(codes::INVALID_ASSIGNMENT_TARGET, &[]));
+        self.advance(); // consume '=' o la asignación compuesta
+        let value = self.parse_assignment()?;
+
+        let span = target.span.to(value.span);
+        let value = Box::new(value);
+        let kind = match (target.kind, operator) {
+            (kind @ (ExpressionKind::Variable(_) | ExpressionKind::Get { .. }), Some(op)) => {
+                let target = Box::new(Expression::new(kind, target.span));
+                ExpressionKind::CompoundAssign { target, op, value }
             }
-            line += 1;
-            column = 1;
-            continue;
+            (ExpressionKind::Variable(variable), None) => ExpressionKind::Assign { variable, value },
+            (ExpressionKind::Get { object, name }, None) => ExpressionKind::Set { object, name, value },
+            _ => return Err(error),
+        };
+        Ok(Expression::new(kind, span))
+    }
+
+    /// `condición ? a : b` (asociativa a la derecha).
+    fn parse_ternary(&mut self) -> Result<Expression, Diagnostic> {
+        let condition = self.parse_binary(0)?;
+
+        if !self.match_token(&[TokenType::Symbol(Symbol::Question)]) {
+            return Ok(condition);
         }
+        let then_branch = self.parse_ternary()?;
+        self.consume(TokenType::Symbol(Symbol::Colon), &tr("E0201.ternary_colon", &[]))?;
+        let else_branch = self.parse_ternary()?;
+
+        let span = condition.span.to(else_branch.span);
+        Ok(Expression::new(
+            ExpressionKind::Ternary {
+                condition: Box::new(condition),
+                then_branch: Box::new(then_branch),
+                else_branch: Box::new(else_branch),
+            },
+            span,
+        ))
+    }
 
-        if ch == '/' && chars.clone().nth(1) == Some('*') {
-            chars.next(); chars.next(); column += 2;
-            let mut closed = false;
-            while let Some(c) = chars.next() {
-                if c == '*' && chars.peek() == Some(&'/') {
-                    chars.next(); column += 2; closed = true; break;
-                }
-                if c == '\n' { line += 1; column = 1; } else { column += 1; }
-            }
-            if !closed {
-                return Err(LemonError::new("Unclosed multiline comment", line, column));
+    /// Operadores binarios por precedencia (Pratt): solo consume operadores
+    /// cuyo poder de enlace izquierdo sea al menos `min_power`.
+    fn parse_binary(&mut self, min_power: u8) -> Result<Expression, Diagnostic> {
+        let mut left = self.parse_unary()?;
+
+        while let Some(op) = token_to_binary_op(self.peek()) {
+            let (left_power, right_power) = binding_power(&op);
+            if left_power < min_power {
+                break;
             }
-            continue;
+            self.advance(); // consume el operador
+            let right = self.parse_binary(right_power)?;
+            let span = left.span.to(right.span);
+            left = Expression::new(
+                ExpressionKind::Binary { left: Box::new(left), op, right: Box::new(right) },
+                span,
+            );
         }
 
-        // =========================
-        // Literales de cadena
-        // =========================
-        if ch == '"' {
-            flush_current(&mut current, &mut tokens, line, start_column);
-            let mut string_value = String::new();
-            chars.next(); column += 1;
-            let mut closed = false;
+        Ok(left)
+    }
 
-            while let Some(c) = chars.next() {
-                column += 1;
-                if c == '\\' {
-                    if let Some(escaped) = chars.next() {
-                        column += 1;
-                        match escaped {
-                            'n' => string_value.push('\n'),
-                            't' => string_value.push('\t'),
-                            '\\' => string_value.push('\\'),
-                            '"' => string_value.push('"'),
-                            _ => return Err(LemonError::new("Invalid escape character", line, column)),
-                        }
-                    }
-                } else if c == '"' {
-                    closed = true; break;
-                } else {
-                    string_value.push(c);
-                }
-            }
+    /// `-x`, `!x`, `not x`
+    fn parse_unary(&mut self) -> Result<Expression, Diagnostic> {
+        if let Some(op) = token_to_unary_op(self.peek()) {
+            let start = self.advance().span; // consume el operador
+            let expr = self.parse_binary(UNARY_BINDING_POWER)?;
+            let span = start.to(expr.span);
+            return Ok(Expression::new(ExpressionKind::Unary { op, expr: Box::new(expr) }, span));
+        }
+        self.parse_call()
+    }
 
-            if !closed {
-                return Err(LemonError::new("Unclosed string literal", line, column));
+    /// Llamadas y accesos encadenados: `a.b(1)(2).c`
+    fn parse_call(&mut self) -> Result<Expression, Diagnostic> {
+        let mut expr = self.parse_primary()?;
+
+        loop {
+            if self.match_token(&[TokenType::Symbol(Symbol::OpenParen)]) {
+                let args = self.parse_arguments()?;
+                let span = self.span_from(expr.span);
+                expr = Expression::new(ExpressionKind::Call { function: Box::new(expr), args }, span);
+            } else if self.match_token(&[TokenType::Symbol(Symbol::Dot)]) {
+                let name = self.consume_property_name()?;
+                let span = self.span_from(expr.span);
+                expr = Expression::new(ExpressionKind::Get { object: Box::new(expr), name }, span);
+            } else {
+                break;
             }
-
-            tokens.push(Token {
-                token_type: TokenType::StringLiteral,
-                value: string_value,
-                line,
-                column: start_column,
-            });
-            continue;
         }
 
-        // =========================
-        // Números (enteros y flotantes)
-        // =========================
-        if ch.is_ascii_digit() {
-            flush_current(&mut current, &mut tokens, line, start_column);
-            let mut number = String::new();
-            start_column = column;
-            let mut dot_found = false;
+        Ok(expr)
+    }
 
-            while let Some(&c) = chars.peek() {
-                if c == '.' {
-                    if dot_found { break; }
-                    if let Some(nxt) = chars.clone().nth(1) {
-                        if nxt.is_ascii_digit() {
-                            dot_found = true;
-                            number.push('.');
-                            chars.next();
-                            column += 1;
-                            continue;
-                        } else {
-                            break;
-                        }
-                    } else {
-                        break;
-                    }
-                } else if c.is_ascii_digit() {
-                    number.push(c);
-                    chars.next();
-                    column += 1;
-                } else {
+    /// Lista de argumentos de una llamada (el `(` ya fue consumido).
+    pub(crate) fn parse_arguments(&mut self) -> Result<Vec<Expression>, Diagnostic> {
+        let mut args = Vec::new();
+        if !self.check(TokenType::Symbol(Symbol::CloseParen)) {
+            loop {
+                args.push(self.parse_expression()?);
+                if !self.match_token(&[TokenType::Symbol(Symbol::Comma)]) {
                     break;
                 }
             }
-
-            tokens.push(Token {
-                token_type: TokenType::Number,
-                value: number,
-                line,
-                column: start_column,
-            });
-            continue;
-        }
-
-        // =========================
-        // Lógicos dobles: &&, ||
-        // =========================
-        if let Some(double_log) = try_double_char_logical(&mut chars) {
-            flush_current(&mut current, &mut tokens, line, start_column);
-            tokens.push(Token {
-                token_type: TokenType::Logical(double_log),
-                value: double_log.to_string(),
-                line,
-                column,
-            });
-            column += 2;
-            continue;
-        }
-
-        // =========================
-        // Lógico unitario: !
-        // =========================
-        if let Some(logical) = crate::grammar::logicals::Logical::from_str(&ch.to_string()) {
-            flush_current(&mut current, &mut tokens, line, start_column);
-            tokens.push(Token {
-                token_type: TokenType::Logical(logical),
-                value: ch.to_string(),
-                line,
-                column,
-            });
-            chars.next(); column += 1;
-            continue;
-        }
-
-        // =========================
-        // Operadores dobles
-        // =========================
-        if let Some(double_op) = try_double_char_operator(&mut chars) {
-            flush_current(&mut current, &mut tokens, line, start_column);
-            tokens.push(Token {
-                token_type: TokenType::Operator(double_op.clone()),
-                value: double_op.to_string(),
-                line,
-                column,
-            });
-            column += 2;
-            continue;
         }
+        self.consume(TokenType::Symbol(Symbol::CloseParen), &tr("E0201.call_close_paren", &[]))?;
+        Ok(args)
+    }
 
-        if ch == '\n' {
-            flush_current(&mut current, &mut tokens, line, start_column);
-            chars.next(); line += 1; column = 1;
-        } else if ch.is_whitespace() {
-            flush_current(&mut current, &mut tokens, line, start_column);
-            chars.next(); column += 1;
-        } else if let Some(symbol) = crate::grammar::symbols::Symbol::from_char(ch) {
-            flush_current(&mut current, &mut tokens, line, start_column);
-            tokens.push(Token {
-                token_type: TokenType::Symbol(symbol.clone()),
-                value: symbol.to_char().to_string(),
-                line,
-                column,
-            });
-            chars.next(); column += 1;
-        } else if ch.is_control() {
-            return Err(LemonError::new("Illegal control character", line, column));
-        } else if !ch.is_ascii_graphic() && !ch.is_whitespace() {
-            return Err(LemonError::new("Illegal character in input", line, column));
-        } else {
-            if current.is_empty() {
-                start_column = column;
+    /// Nombre de propiedad después de `.`; además de identificadores acepta
+    /// palabras reservadas y nombres de tipo (`obj.type`, `datos.map`).
+    fn consume_property_name(&mut self) -> Result<String, Diagnostic> {
+        match self.peek().token_type {
+            TokenType::Identifier | TokenType::Keyword(_) | TokenType::Type(_) => {
+                Ok(self.advance().value.to_string())
             }
-            current.push(ch);
-            chars.next(); column += 1;
+            _ => Err(self.error_at_current(codes::UNEXPECTED_TOKEN, &tr("E0201.property_name", &[]))),
         }
     }
 
-    flush_current(&mut current, &mut tokens, line, start_column);
-
-    tokens.push(Token {
-        token_type: TokenType::EOF,
-        value: "<EOF>".to_string(),
-        line,
-        column,
-    });
-
-    Ok(tokens)
-}
-
-fn flush_current(current: &mut String, tokens: &mut Vec<Token>, line: usize, column: usize) {
-    if current.is_empty() { return; }
-    tokens.push(classify(current, line, column));
-    current.clear();
-}
+    /// Literales, variables y agrupaciones.
+    fn parse_primary(&mut self) -> Result<Expression, Diagnostic> {
+        let token = self.peek().clone();
+
+        let kind = match token.token_type {
+            // El lexer ya validó el literal; el error solo cubre tokens armados a mano
+            TokenType::Integer => match lexer::integer_value(&token.value) {
+                Some(value) => ExpressionKind::Integer(value),
+                None => return Err(self.error_at_current(codes::INVALID_NUMBER, &tr(codes::INVALID_NUMBER, &[]))),
+            },
+            TokenType::Float => match lexer::float_value(&token.value) {
+                Some(value) => ExpressionKind::Float(value),
+                None => return Err(self.error_at_current(codes::INVALID_NUMBER, &tr(codes::INVALID_NUMBER, &[]))),
+            },
+            TokenType::StringLiteral => ExpressionKind::String(token.value.to_string()),
+            TokenType::InterpolationStart => return self.parse_interpolated(),
+            TokenType::Keyword(Keyword::True) => ExpressionKind::Boolean(true),
+            TokenType::Keyword(Keyword::False) => ExpressionKind::Boolean(false),
+            TokenType::Keyword(Keyword::None) | TokenType::Type(Type::Null) => ExpressionKind::None,
+            TokenType::Identifier => ExpressionKind::Variable(token.value.to_string()),
+
+            // Los nombres de tipo funcionan como funciones de conversión: `string(42)`, `list(1, 2)`
+            TokenType::Type(_) => ExpressionKind::Variable(token.value.to_lowercase()),
+
+            TokenType::Symbol(Symbol::OpenParen) => {
+                self.advance(); // consume '('
+                let inner = self.parse_expression()?;
+                self.consume(TokenType::Symbol(Symbol::CloseParen), &tr("E0201.group_close_paren", &[]))?;
+                return Ok(Expression::new(ExpressionKind::Grouping(Box::new(inner)), self.span_from(token.span)));
+            }
 
-fn classify(word: &str, line: usize, column: usize) -> Token {
-    use TokenType::*;
-    let value = word.to_string();
+            _ => return Err(self.error_at_current(codes::EXPECTED_EXPRESSION, &tr(codes::EXPECTED_EXPRESSION, &[]))),
+        };
 
-    if let Some(kw) = crate::grammar::keywords::Keyword::from_str(word) {
-        return Token { token_type: Keyword(kw), value, line, column };
-    }
-    if let Some(ty) = crate::grammar::types::Type::from_str(word) {
-        return Token { token_type: Type(ty), value, line, column };
-    }
-    if let Some(op) = crate::grammar::operators::Operator::from_str(word) {
-        return Token { token_type: Operator(op), value, line, column };
-    }
-    if let Some(cmp) = crate::grammar::comparators::Comparator::from_str(word) {
-        return Token { token_type: Comparator(cmp), value, line, column };
-    }
-    if let Some(log) = crate::grammar::logicals::Logical::from_str(word) {
-        return Token { token_type: Logical(log), value, line, column };
-    }
-    if let Some(comment) = crate::grammar::comments::Comment::from_str(word) {
-        return Token { token_type: Comment(comment), value, line, column };
+        self.advance();
+        Ok(Expression::new(kind, token.span))
     }
 
-    if word.starts_with(|c: char| c.is_ascii_digit()) {
-        return Token { token_type: Unknown, value, line, column };
-    }
+    /// Cadena interpolada: el lexer ya la dividió en fragmentos de texto y
+    /// expresiones entre `{` y `}`, todo entre `InterpolationStart` e
+    /// `InterpolationEnd`.
+    fn parse_interpolated(&mut self) -> Result<Expression, Diagnostic> {
+        let start = self.advance().span; // consume la comilla de apertura
+        let mut parts = Vec::new();
+
+        while !self.match_token(&[TokenType::InterpolationEnd]) {
+            if self.check(TokenType::StringFragment) {
+                parts.push(InterpolationPart::Literal(self.advance().value.to_string()));
+                continue;
+            }
+            self.consume(TokenType::Symbol(Symbol::OpenBrace), &tr("E0201.interpolation_close", &[]))?;
+            parts.push(InterpolationPart::Expression(self.parse_expression()?));
+            self.consume(TokenType::Symbol(Symbol::CloseBrace), &tr("E0201.interpolation_close", &[]))?;
+        }
 
-    if word.chars().all(|c| c.is_alphanumeric() || c == '_') {
-        return Token { token_type: Identifier, value, line, column };
+        Ok(Expression::new(ExpressionKind::Interpolated(parts), self.span_from(start)))
     }
-
-    Token { token_type: Unknown, value, line, column }
-}
-
-
-fn try_double_char_operator(
-    chars: &mut std::iter::Peekable<std::str::Chars>,
-) -> Option<crate::grammar::operators::Operator> {
-    let ch1 = chars.peek().copied()?;
-    let ch2 = chars.clone().nth(1)?;
-    let pair = format!("{}{}", ch1, ch2);
-
-    crate::grammar::operators::Operator::from_str(&pair).map(|op| {
-        chars.next(); chars.next(); op
-    })
-}
-
-fn try_double_char_logical(
-    chars: &mut std::iter::Peekable<std::str::Chars>,
-) -> Option<crate::grammar::logicals::Logical> {
-    let ch1 = chars.peek().copied()?;
-    let ch2 = chars.clone().nth(1)?;
-    let pair = format!("{}{}", ch1, ch2);
-
-    crate::grammar::logicals::Logical::from_str(&pair).map(|log| {
-        chars.next(); chars.next(); log
-    })
 }
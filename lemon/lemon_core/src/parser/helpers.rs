@@ -1,7 +1,6 @@
 use crate::token::{Token, TokenType};
 use crate::grammar::*;
 use crate::ast::{BinaryOp, UnaryOp};
-use crate::parser::Parser;
 
 /// Determina si un token representa un identificador válido.
 /// Un identificador es típicamente un nombre de variable, función, etc.
@@ -17,7 +16,8 @@ pub fn is_literal(token: &Token) -> bool {
         TokenType::Keyword(Keyword::True)
             | TokenType::Keyword(Keyword::False)
             | TokenType::Type(Type::Null)
-            | TokenType::Number
+            | TokenType::Integer
+            | TokenType::Float
             | TokenType::Type(Type::String)
     )
 }
@@ -30,6 +30,8 @@ pub fn token_to_binary_op(token: &Token) -> Option<BinaryOp> {
         TokenType::Operator(Operator::Subtract) => Some(BinaryOp::Sub),// -
         TokenType::Operator(Operator::Multiply) => Some(BinaryOp::Mul),// *
         TokenType::Operator(Operator::Divide) => Some(BinaryOp::Div),  // /
+        TokenType::Operator(Operator::Modulus) => Some(BinaryOp::Mod), // %
+        TokenType::Operator(Operator::Power) => Some(BinaryOp::Pow),   // ^
         TokenType::Comparator(Comparator::Equal) => Some(BinaryOp::Eq),         // ==
         TokenType::Comparator(Comparator::NotEqual) => Some(BinaryOp::Neq),     // !=
         TokenType::Comparator(Comparator::Greater) => Some(BinaryOp::Gt),       // >
@@ -42,6 +44,26 @@ pub fn token_to_binary_op(token: &Token) -> Option<BinaryOp> {
     }
 }
 
+/// Poder de enlace (izquierdo, derecho) de cada operador binario para el parser Pratt.
+/// Un número mayor enlaza más fuerte. Si el derecho es menor que el izquierdo,
+/// el operador es asociativo a la derecha (como `^`).
+pub fn binding_power(op: &BinaryOp) -> (u8, u8) {
+    match op {
+        BinaryOp::Or => (1, 2),
+        BinaryOp::And => (3, 4),
+        BinaryOp::Eq | BinaryOp::Neq => (5, 6),
+        BinaryOp::Gt | BinaryOp::Gte | BinaryOp::Lt | BinaryOp::Lte => (7, 8),
+        BinaryOp::Add | BinaryOp::Sub => (9, 10),
+        BinaryOp::Mul | BinaryOp::Div | BinaryOp::Mod => (11, 12),
+        BinaryOp::Pow => (15, 14),
+    }
+}
+
+/// Poder de enlace de los operadores unarios prefijos (`-x`, `!x`).
+/// Está entre la multiplicación y la potencia: `-a * b` es `(-a) * b`
+/// y `-a ^ b` es `-(a ^ b)`.
+pub const UNARY_BINDING_POWER: u8 = 13;
+
 /// Convierte un token en su operador unario correspondiente en el AST, si aplica.
 /// Retorna `Some(UnaryOp)` si el token es un operador unario válido, o `None` si no lo es.
 pub fn token_to_unary_op(token: &Token) -> Option<UnaryOp> {
@@ -2,16 +2,29 @@
 pub mod expressions;  // Módulo que maneja el parsing de expresiones
 pub mod helpers;      // Utilidades auxiliares para parsing
 pub mod statements;   // Módulo que maneja el parsing de statements (instrucciones)
+pub mod types;        // Módulo que maneja el parsing de anotaciones de tipo
 
 pub use crate::token::{Token, TokenType};           // Reexporta tipos relacionados con los tokens
 pub use crate::ast::{statements::Statement};        // Reexporta el tipo Statement del AST
+use crate::diagnostic::{codes, Diagnostic};
+use crate::grammar::{Keyword, Symbol, BLOCK_BOUNDARIES};
+use crate::i18n::tr;
+use crate::lexer::Lexer;
+use crate::span::Span;
 
 /// Estructura principal del parser.
 /// Contiene la lista de tokens generados por el lexer y un índice (`current`)
 /// que indica cuál es el token que se está procesando actualmente.
+///
+/// Los tokens pueden llegar completos (`new`) o pedirse al lexer a medida
+/// que se avanza (`from_lexer`); en ese caso la lista crece de a poco.
 pub struct Parser {
-    tokens: Vec<Token>,  // Lista completa de tokens de entrada
-    current: usize,      // Índice actual dentro del vector de tokens
+    tokens: Vec<Token>,            // Tokens de entrada (los leídos hasta ahora si vienen de un `Lexer`)
+    lexer: Option<Lexer>,          // Origen de los tokens que faltan leer
+    current: usize,                // Índice actual dentro del vector de tokens
+    diagnostics: Vec<Diagnostic>,  // Errores de sintaxis acumulados durante el análisis
+    ui: bool,                      // Dentro de una sección `ui` (ver `contextual_keyword`)
+    branch: bool,                  // El bloque actual es una rama de `if ... end`: `else` y `elif` la cierran
 }
 
 impl Parser {
@@ -22,7 +35,39 @@ impl Parser {
     /// Crea una nueva instancia del parser a partir de una lista de tokens.
     /// Este parser recorrerá y analizará los tokens para producir un AST.
     pub fn new(tokens: Vec<Token>) -> Self {
-        Self { tokens, current: 0 }
+        Self { tokens, lexer: None, current: 0, diagnostics: Vec::new(), ui: false, branch: false }
+    }
+
+    /// Crea un parser que pide los tokens al lexer a medida que los
+    /// necesita. Se analiza la entrada recibida hasta ahora (el parser
+    /// llama a `finish`); los errores léxicos se suman a los de sintaxis.
+    ///
+    /// ```
+    /// # use lemon_core::lexer::Lexer;
+    /// # use lemon_core::parser::Parser;
+    /// let mut lexer = Lexer::new("let x = 1\n");
+    /// lexer.push_str("print(x + @)\n");
+    /// let (statements, diagnostics) = Parser::from_lexer(lexer).parse();
+    /// assert_eq!(statements.len(), 1);
+    /// assert_eq!(diagnostics[0].code, "E0106"); // `@`, informado por el lexer
+    /// ```
+    pub fn from_lexer(mut lexer: Lexer) -> Self {
+        lexer.finish();
+        let mut parser = Self { tokens: Vec::new(), lexer: Some(lexer), current: 0, diagnostics: Vec::new(), ui: false, branch: false };
+        parser.fill(1);
+        parser
+    }
+
+    /// Pide tokens al lexer hasta tener el de la posición `index` (o el `EOF`).
+    fn fill(&mut self, index: usize) {
+        let Some(lexer) = &mut self.lexer else { return };
+        while self.tokens.len() <= index {
+            match lexer.next() {
+                Some(Ok(token)) => self.tokens.push(token),
+                Some(Err(diagnostic)) => self.diagnostics.push(diagnostic),
+                None => break,
+            }
+        }
     }
 
     // ========================
@@ -30,19 +75,58 @@ impl Parser {
     // ========================
 
     /// Inicia el proceso de parsing completo.
-    /// Recorre todos los tokens mientras no se llegue al final,
-    /// intentando generar una lista de statements válidos (el AST).
-    pub fn parse(&mut self) -> Result<Vec<Statement>, String> {
-        let mut statements = Vec::new();
+    /// Recorre todos los tokens mientras no se llegue al final y devuelve
+    /// el AST junto con todos los errores de sintaxis encontrados.
+    ///
+    /// Un error no detiene el análisis: la instrucción que falló se descarta,
+    /// el parser se resincroniza (ver `synchronize`) y sigue con la próxima.
+    /// Si hubo errores, el AST es parcial pero sigue siendo útil para herramientas.
+    pub fn parse(&mut self) -> (Vec<Statement>, Vec<Diagnostic>) {
+        let statements = self.parse_block(&[]);
+        (statements, std::mem::take(&mut self.diagnostics))
+    }
+
+    // ========================
+    // Recuperación de errores
+    // ========================
+
+    /// Registra un error de sintaxis para informarlo al final.
+    ///
+    /// Los errores sobre un token `Unknown` se descartan: el lexer ya informó
+    /// ese token y repetirlo solo agregaría ruido.
+    pub fn report(&mut self, diagnostic: Diagnostic) {
+        let on_unknown = diagnostic.span().is_some_and(|span| {
+            self.tokens.iter().any(|t| t.token_type == TokenType::Unknown && t.span == span)
+        });
+        if !on_unknown {
+            self.diagnostics.push(diagnostic);
+        }
+    }
 
+    /// Modo pánico: descarta tokens hasta un límite de instrucción, para que
+    /// un error no provoque una cascada de errores falsos.
+    ///
+    /// Se detiene después de un `;` o salto de línea, antes del primer token de una línea nueva
+    /// o antes de lo que cierra un bloque (`end`, `elif`, `else`, `fail`, `}`),
+    /// que así queda disponible para el bloque que la espera.
+    pub fn synchronize(&mut self) {
         while !self.is_at_end() {
-            match self.parse_statement() {
-                Ok(stmt) => statements.push(stmt),
-                Err(e) => return Err(e), // Si ocurre un error, el análisis se detiene inmediatamente
+            if self.current > 0 {
+                let previous = self.previous();
+                if matches!(previous.token_type, TokenType::Symbol(Symbol::Semicolon) | TokenType::Newline) {
+                    return;
+                }
+                if self.peek().line > previous.line {
+                    return;
+                }
             }
+            if BLOCK_BOUNDARIES.iter().any(|&kw| self.check(TokenType::Keyword(kw)))
+                || self.check(TokenType::Symbol(Symbol::CloseBrace))
+            {
+                return;
+            }
+            self.advance();
         }
-
-        Ok(statements) // AST final construido con éxito
     }
 
     // ========================
@@ -59,6 +143,13 @@ impl Parser {
         &self.tokens[self.current]
     }
 
+    /// Retorna el token siguiente al actual sin consumir nada.
+    /// Si el actual es el último, retorna el mismo token (EOF).
+    pub fn peek_next(&self) -> &Token {
+        let index = (self.current + 1).min(self.tokens.len() - 1);
+        &self.tokens[index]
+    }
+
     /// Retorna el token que acaba de ser consumido (el anterior al actual).
     pub fn previous(&self) -> &Token {
         &self.tokens[self.current - 1]
@@ -69,10 +160,17 @@ impl Parser {
     pub fn advance(&mut self) -> &Token {
         if !self.is_at_end() {
             self.current += 1;
+            self.fill(self.current + 1); // `peek_next` mira un token más adelante
         }
         self.previous()
     }
 
+    /// Rango desde `start` hasta el final del último token consumido.
+    /// Se usa para calcular el `span` de un nodo al terminar de analizarlo.
+    pub fn span_from(&self, start: Span) -> Span {
+        start.to(self.previous().span)
+    }
+
     /// Verifica si el token actual coincide exactamente con el tipo dado.
     pub fn check(&self, token_type: TokenType) -> bool {
         !self.is_at_end() && self.peek().token_type == token_type
@@ -91,21 +189,40 @@ impl Parser {
     }
 
     /// Consume un token del tipo esperado. Si no coincide, lanza un error con el mensaje dado.
-    pub fn consume(&mut self, token_type: TokenType, message: &str) -> Result<Token, String> {
+    pub fn consume(&mut self, token_type: TokenType, message: &str) -> Result<Token, Diagnostic> {
         if self.check(token_type) {
             Ok(self.advance().clone())
         } else {
-            Err(format!(
-                "[Línea {}] Error de sintaxis: {} (en '{}')",
-                self.peek().line, message, self.peek().value
-            ))
+            Err(self.error_at_current(codes::UNEXPECTED_TOKEN, message))
         }
     }
 
+    /// Construye un error de sintaxis cuyo rango principal es el token actual.
+    pub fn error_at_current(&self, code: &'static str, message: &str) -> Diagnostic {
+        let token = self.peek();
+        if token.token_type == TokenType::EOF {
+            // Al final del archivo se señala justo después del último token (sin contar saltos de línea)
+            let last = self.tokens[..self.current].iter().rev().find(|t| t.token_type != TokenType::Newline);
+            if let Some(last) = last {
+                let span = Span::point(last.span.end);
+                return Diagnostic::error(code, message).with_label(span, &tr("label.eof", &[]));
+            }
+        }
+        if token.token_type == TokenType::Newline {
+            return Diagnostic::error(code, message).with_label(token.span, &tr("label.newline", &[]));
+        }
+        Diagnostic::error(code, message).with_label(token.span, &tr("label.found", &[&token.value]))
+    }
+
+    /// Consume el `end` que cierra un bloque `block` (`if`, `while`, `fn`, ...).
+    pub fn consume_end(&mut self, block: &str) -> Result<Token, Diagnostic> {
+        self.consume(TokenType::Keyword(Keyword::End), &tr("E0201.block_end", &[&block]))
+    }
+
     /// Consume un token que debe ser un identificador válido y retorna su valor (texto).
     /// Si no es un identificador, lanza un error con el mensaje proporcionado.
-    pub fn consume_identifier(&mut self, message: &str) -> Result<String, String> {
+    pub fn consume_identifier(&mut self, message: &str) -> Result<String, Diagnostic> {
         let token = self.consume(TokenType::Identifier, message)?;
-        Ok(token.value.clone())
+        Ok(token.value.to_string())
     }
 }
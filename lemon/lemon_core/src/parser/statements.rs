@@ -1,287 +1,604 @@
-use crate::token::{Token, TokenType};
-use crate::error::LemonError;
-
-/// Función principal: convierte el código fuente en una lista de tokens
-pub fn tokenize(code: &str) -> Result<Vec<Token>, LemonError> {
-    let mut tokens = Vec::new();
-    let mut current = String::new();
-    let mut chars = code.chars().peekable();
-    let mut line = 1;
-    let mut column = 1;
-    let mut start_column = column;
-
-    while let Some(&ch) = chars.peek() {
-        // =========================
-        // Comentarios
-        // =========================
-        if ch == '/' && chars.clone().nth(1) == Some('/') {
-            while let Some(&c) = chars.peek() {
-                chars.next();
-                column += 1;
-                if c == '\n' {
-                    break;
-                }
-            }
-            line += 1;
-            column = 1;
-            continue;
-        }
+use crate::ast::{Deprecation, Expression, ExpressionKind, NodeId, Param, Statement, StatementKind};
+use crate::diagnostic::{codes, Diagnostic};
+use crate::grammar::*;
+use crate::i18n::tr;
+use crate::parser::Parser;
+use crate::span::Span;
+use crate::token::TokenType;
 
-        if ch == '/' && chars.clone().nth(1) == Some('*') {
-            chars.next(); chars.next(); column += 2;
-            let mut closed = false;
-            while let Some(c) = chars.next() {
-                if c == '*' && chars.peek() == Some(&'/') {
-                    chars.next(); column += 2; closed = true; break;
+/// Métodos del parser dedicados a las instrucciones (statements).
+///
+/// La sintaxis de bloques sigue la documentación (`Docs/examples.md`):
+/// cada estructura abre un bloque que se cierra con `end`. También se acepta
+/// el estilo C, con el cuerpo entre `{` y `}` y la condición entre paréntesis
+/// (`if (x > 0) { ... }`); ambos estilos producen el mismo AST.
+///
+/// Las instrucciones terminan en un salto de línea o en `;` (opcional al
+/// final de la línea).
+impl Parser {
+    /// Analiza una instrucción completa, incluido su `;` o salto de línea final.
+    pub fn parse_statement(&mut self) -> Result<Statement, Diagnostic> {
+        let start = self.peek().span;
+        let kind = match self.peek().token_type {
+            TokenType::Keyword(Keyword::Let) => {
+                self.advance();
+                self.parse_let()?
+            }
+            TokenType::Keyword(Keyword::Del) => {
+                self.advance();
+                self.parse_delete()?
+            }
+            TokenType::Keyword(Keyword::Old) => {
+                self.advance();
+                self.parse_deprecated()?
+            }
+            TokenType::Keyword(Keyword::If) => {
+                self.advance();
+                self.parse_if()?
+            }
+            TokenType::Keyword(Keyword::While) => {
+                self.advance();
+                self.parse_while()?
+            }
+            TokenType::Keyword(Keyword::Do) => {
+                self.advance();
+                self.parse_do_while()?
+            }
+            TokenType::Keyword(Keyword::For) => {
+                self.advance();
+                self.parse_for()?
+            }
+            TokenType::Keyword(Keyword::Fn) => {
+                self.advance();
+                self.parse_function()?
+            }
+            TokenType::Keyword(Keyword::Try) => {
+                self.advance();
+                self.parse_try()?
+            }
+            TokenType::Keyword(Keyword::Return) | TokenType::Keyword(Keyword::Back) => {
+                self.advance();
+                self.parse_return()?
+            }
+            TokenType::Keyword(Keyword::Print) => {
+                self.advance();
+                StatementKind::BuiltinCall("print".to_string(), self.parse_print_arguments()?)
+            }
+            TokenType::Keyword(Keyword::Break) => {
+                self.advance();
+                StatementKind::Break
+            }
+            TokenType::Keyword(Keyword::Continue) => {
+                self.advance();
+                StatementKind::Continue
+            }
+            TokenType::Keyword(Keyword::Ui) => {
+                self.advance();
+                self.ui = true;
+                StatementKind::Ui
+            }
+            TokenType::Keyword(Keyword::Wrap) => {
+                self.advance();
+                self.parse_ui_element(Keyword::Wrap)?
+            }
+            TokenType::Identifier => match self.contextual_keyword() {
+                Some(element) => {
+                    self.advance();
+                    self.parse_ui_element(element)?
                 }
-                if c == '\n' { line += 1; column = 1; } else { column += 1; }
+                None => StatementKind::Expr(self.parse_expression()?),
+            },
+            TokenType::Keyword(kw) if BLOCK_BOUNDARIES.contains(&kw) => {
+                return Err(self.error_at_current(codes::UNEXPECTED_BLOCK_CLOSE, &tr(codes::UNEXPECTED_BLOCK_CLOSE, &[])));
             }
-            if !closed {
-                return Err(LemonError::new("Unclosed multiline comment", line, column));
+            TokenType::Symbol(Symbol::CloseBrace) => {
+                return Err(self.error_at_current(codes::UNEXPECTED_BLOCK_CLOSE, &tr(codes::UNEXPECTED_BLOCK_CLOSE, &[])));
             }
-            continue;
+            _ => StatementKind::Expr(self.parse_expression()?),
+        };
+
+        let stmt = Statement::new(kind, self.span_from(start));
+        self.end_statement();
+        Ok(stmt)
+    }
+
+    /// Consume el `;` o salto de línea que termina una instrucción. Si la
+    /// instrucción termina justo antes de un cierre de bloque o del final del
+    /// archivo no hace falta ninguno.
+    ///
+    /// Sin separador (`print 1 print 2`) se informa el error y se descarta el
+    /// resto de la línea, pero la instrucción ya analizada se conserva.
+    fn end_statement(&mut self) {
+        if self.match_token(&[TokenType::Symbol(Symbol::Semicolon), TokenType::Newline]) || self.at_statement_boundary() {
+            return;
         }
+        let error = self.error_at_current(codes::UNEXPECTED_TOKEN, &tr("E0201.statement_end", &[]));
+        self.report(error);
+        self.synchronize();
+    }
 
-        // =========================
-        // Literales de cadena
-        // =========================
-        if ch == '"' {
-            flush_current(&mut current, &mut tokens, line, start_column);
-            let mut string_value = String::new();
-            chars.next(); column += 1;
-            let mut closed = false;
-
-            while let Some(c) = chars.next() {
-                column += 1;
-                if c == '\\' {
-                    if let Some(escaped) = chars.next() {
-                        column += 1;
-                        match escaped {
-                            'n' => string_value.push('\n'),
-                            't' => string_value.push('\t'),
-                            '\\' => string_value.push('\\'),
-                            '"' => string_value.push('"'),
-                            _ => return Err(LemonError::new("Invalid escape character", line, column)),
-                        }
-                    }
-                } else if c == '"' {
-                    closed = true; break;
-                } else {
-                    string_value.push(c);
-                }
-            }
+    // ========================
+    // Declaraciones
+    // ========================
 
-            if !closed {
-                return Err(LemonError::new("Unclosed string literal", line, column));
-            }
+    /// `let nombre` o `let nombre = valor` (el `let` ya fue consumido). El
+    /// atributo `sure` va después del nombre y del tipo, y exige un valor:
+    /// `let PI: float sure = 3.1416`.
+    fn parse_let(&mut self) -> Result<StatementKind, Diagnostic> {
+        let name = self.consume_identifier(&tr("E0201.let_name", &[]))?;
+        let ty = self.parse_type_annotation()?;
+        let sure = self.match_token(&[TokenType::Keyword(Keyword::Sure)]);
+
+        if self.match_token(&[TokenType::Symbol(Symbol::Define)]) {
+            let value = self.parse_expression()?;
+            Ok(StatementKind::Let { name, ty, sure, deprecated: None, value })
+        } else if sure {
+            Err(self.error_at_current(codes::UNEXPECTED_TOKEN, &tr("E0201.sure_value", &[])))
+        } else {
+            Ok(StatementKind::Variable { name, ty, initializer: None, deprecated: None })
+        }
+    }
+
+    /// `old let ...` u `old fn ...` (el `old` ya fue consumido), con un
+    /// mensaje opcional entre paréntesis: `old("usa nueva") fn vieja()`.
+    fn parse_deprecated(&mut self) -> Result<StatementKind, Diagnostic> {
+        let start = self.previous().span;
+        let message = if self.match_token(&[TokenType::Symbol(Symbol::OpenParen)]) {
+            let message = self.consume(TokenType::StringLiteral, &tr("E0201.old_message", &[]))?;
+            let message = message.value.to_string();
+            self.consume(TokenType::Symbol(Symbol::CloseParen), &tr("E0201.old_close_paren", &[]))?;
+            Some(message)
+        } else {
+            None
+        };
+        let deprecation = Deprecation { message, span: self.span_from(start) };
 
-            tokens.push(Token {
-                token_type: TokenType::StringLiteral,
-                value: string_value,
-                line,
-                column: start_column,
-            });
-            continue;
+        let mut kind = if self.match_token(&[TokenType::Keyword(Keyword::Let)]) {
+            self.parse_let()?
+        } else if self.match_token(&[TokenType::Keyword(Keyword::Fn)]) {
+            self.parse_function()?
+        } else {
+            return Err(self.error_at_current(codes::UNEXPECTED_TOKEN, &tr("E0201.old_target", &[])));
+        };
+        match &mut kind {
+            StatementKind::Let { deprecated, .. }
+            | StatementKind::Variable { deprecated, .. }
+            | StatementKind::Function { deprecated, .. } => *deprecated = Some(deprecation),
+            _ => {}
         }
+        Ok(kind)
+    }
 
-        // =========================
-        // Números (enteros y flotantes)
-        // =========================
-        if ch.is_ascii_digit() {
-            flush_current(&mut current, &mut tokens, line, start_column);
-            let mut number = String::new();
-            start_column = column;
-            let mut dot_found = false;
-
-            while let Some(&c) = chars.peek() {
-                if c == '.' {
-                    if dot_found { break; }
-                    if let Some(nxt) = chars.clone().nth(1) {
-                        if nxt.is_ascii_digit() {
-                            dot_found = true;
-                            number.push('.');
-                            chars.next();
-                            column += 1;
-                            continue;
-                        } else {
-                            break;
-                        }
-                    } else {
-                        break;
-                    }
-                } else if c.is_ascii_digit() {
-                    number.push(c);
-                    chars.next();
-                    column += 1;
-                } else {
+    /// `del nombre` (el `del` ya fue consumido).
+    fn parse_delete(&mut self) -> Result<StatementKind, Diagnostic> {
+        let name = self.consume_identifier(&tr("E0201.del_name", &[]))?;
+        Ok(StatementKind::Delete(name))
+    }
+
+    /// `fn nombre(a, b) ... end` o `fun nombre(a, b) { ... }` (el `fn` ya fue
+    /// consumido). El nombre es opcional, y los parámetros y el valor devuelto
+    /// pueden llevar tipo: `fn doble(x: int) -> int`.
+    fn parse_function(&mut self) -> Result<StatementKind, Diagnostic> {
+        let name = if self.check(TokenType::Identifier) {
+            Some(self.advance().value.to_string())
+        } else {
+            None
+        };
+
+        self.consume(TokenType::Symbol(Symbol::OpenParen), &tr("E0201.fn_open_paren", &[]))?;
+        let mut params = Vec::new();
+        if !self.check(TokenType::Symbol(Symbol::CloseParen)) {
+            loop {
+                let start = self.peek().span;
+                let name = self.consume_identifier(&tr("E0201.param_name", &[]))?;
+                let ty = self.parse_type_annotation()?;
+                params.push(Param { id: NodeId::next(), name, ty, span: self.span_from(start) });
+                if !self.match_token(&[TokenType::Symbol(Symbol::Comma)]) {
                     break;
                 }
             }
-
-            tokens.push(Token {
-                token_type: TokenType::Number,
-                value: number,
-                line,
-                column: start_column,
-            });
-            continue;
         }
+        self.consume(TokenType::Symbol(Symbol::CloseParen), &tr("E0201.fn_close_paren", &[]))?;
+        let return_type = if self.match_token(&[TokenType::Symbol(Symbol::Arrow)]) {
+            Some(self.parse_type()?)
+        } else {
+            None
+        };
+
+        let body = if self.at_brace_block() {
+            self.parse_brace_statements()?
+        } else {
+            let body = self.parse_block(&[Keyword::End]);
+            self.consume_end("fn")?;
+            body
+        };
 
-        // =========================
-        // Lógicos dobles: &&, ||
-        // =========================
-        if let Some(double_log) = try_double_char_logical(&mut chars) {
-            flush_current(&mut current, &mut tokens, line, start_column);
-            tokens.push(Token {
-                token_type: TokenType::Logical(double_log),
-                value: double_log.to_string(),
-                line,
-                column,
-            });
-            column += 2;
-            continue;
+        Ok(StatementKind::Function { name, params, return_type, deprecated: None, body })
+    }
+
+    // ========================
+    // Control de flujo
+    // ========================
+
+    /// `if cond ... elif cond ... else ... end` (el `if` o `elif` ya fue consumido).
+    ///
+    /// Cada `elif` se representa como un `If` anidado en la rama `else`,
+    /// y todos comparten el mismo `end` final.
+    ///
+    /// En estilo C cada rama tiene sus llaves: `if (c) { } else if (c) { } else { }`.
+    fn parse_if(&mut self) -> Result<StatementKind, Diagnostic> {
+        let condition = self.parse_condition()?;
+        if self.at_brace_block() {
+            return self.parse_brace_if(condition);
         }
+        let then_branch = self.parse_block_statement(&BLOCK_BOUNDARIES[..3]);
+
+        let else_branch = if self.match_token(&[TokenType::Keyword(Keyword::Elif)]) {
+            let start = self.previous().span;
+            let kind = self.parse_if()?;
+            Some(Box::new(Statement::new(kind, self.span_from(start))))
+        } else if self.match_token(&[TokenType::Keyword(Keyword::Else)]) {
+            let branch = self.parse_block_statement(&[Keyword::End]);
+            self.consume_end("if")?;
+            Some(Box::new(branch))
+        } else {
+            self.consume_end("if")?;
+            None
+        };
+
+        Ok(StatementKind::If { condition, then_branch: Box::new(then_branch), else_branch })
+    }
 
-        // =========================
-        // Lógico unitario: !
-        // =========================
-        if let Some(logical) = crate::grammar::logicals::Logical::from_str(&ch.to_string()) {
-            flush_current(&mut current, &mut tokens, line, start_column);
-            tokens.push(Token {
-                token_type: TokenType::Logical(logical),
-                value: ch.to_string(),
-                line,
-                column,
-            });
-            chars.next(); column += 1;
-            continue;
+    /// Resto de un `if` en estilo C, desde la `{` de la primera rama.
+    ///
+    /// `else` y `elif` pueden ir en la línea siguiente a la `}`, salvo dentro
+    /// de una rama de `if ... end`: ahí, en otra línea, son de ese `if`.
+    fn parse_brace_if(&mut self, condition: Expression) -> Result<StatementKind, Diagnostic> {
+        let then_branch = self.parse_brace_block()?;
+
+        let else_branch = if self.match_branch(Keyword::Else) {
+            let start = self.previous().span;
+            if self.match_token(&[TokenType::Keyword(Keyword::If)]) {
+                // `else if` equivale a `elif`
+                let kind = self.parse_if()?;
+                Some(Box::new(Statement::new(kind, self.span_from(start))))
+            } else {
+                Some(Box::new(self.parse_body("if")?))
+            }
+        } else if self.match_branch(Keyword::Elif) {
+            let start = self.previous().span;
+            let kind = self.parse_if()?;
+            Some(Box::new(Statement::new(kind, self.span_from(start))))
+        } else {
+            None
+        };
+
+        Ok(StatementKind::If { condition, then_branch: Box::new(then_branch), else_branch })
+    }
+
+    /// Argumentos de `print` (ya consumido): `print x`, `print(x)` o
+    /// `print(a, b)`, los mismos que acepta la función integrada. Si la
+    /// expresión sigue después del `)`, como en `print (a + b) * 2`, los
+    /// paréntesis eran una agrupación.
+    fn parse_print_arguments(&mut self) -> Result<Vec<Expression>, Diagnostic> {
+        let start = self.current;
+        if self.match_token(&[TokenType::Symbol(Symbol::OpenParen)]) {
+            let args = self.parse_arguments()?;
+            if args.len() != 1 || self.at_statement_boundary() {
+                return Ok(args);
+            }
+            self.current = start;
         }
+        Ok(vec![self.parse_expression()?])
+    }
+
+    /// `while cond ... end` (el `while` ya fue consumido).
+    fn parse_while(&mut self) -> Result<StatementKind, Diagnostic> {
+        let condition = self.parse_condition()?;
+        let body = self.parse_body("while")?;
+
+        Ok(StatementKind::While { condition, body: Box::new(body) })
+    }
 
-        // =========================
-        // Operadores dobles
-        // =========================
-        if let Some(double_op) = try_double_char_operator(&mut chars) {
-            flush_current(&mut current, &mut tokens, line, start_column);
-            tokens.push(Token {
-                token_type: TokenType::Operator(double_op.clone()),
-                value: double_op.to_string(),
-                line,
-                column,
-            });
-            column += 2;
-            continue;
+    /// `do ... while cond end` (el `do` ya fue consumido).
+    ///
+    /// Dentro del cuerpo, el primer `while` cierra el bloque; un bucle `while`
+    /// anidado debe ir dentro de otra estructura. En estilo C no lleva `end`:
+    /// `do { ... } while (cond)`.
+    fn parse_do_while(&mut self) -> Result<StatementKind, Diagnostic> {
+        if self.at_brace_block() {
+            let body = self.parse_brace_block()?;
+            if !self.match_after_newlines(TokenType::Keyword(Keyword::While)) {
+                return Err(self.error_at_current(codes::UNEXPECTED_TOKEN, &tr("E0201.do_while", &[])));
+            }
+            let condition = self.parse_condition()?;
+            return Ok(StatementKind::DoWhile { body: Box::new(body), condition });
         }
 
-        if ch == '\n' {
-            flush_current(&mut current, &mut tokens, line, start_column);
-            chars.next(); line += 1; column = 1;
-        } else if ch.is_whitespace() {
-            flush_current(&mut current, &mut tokens, line, start_column);
-            chars.next(); column += 1;
-        } else if let Some(symbol) = crate::grammar::symbols::Symbol::from_char(ch) {
-            flush_current(&mut current, &mut tokens, line, start_column);
-            tokens.push(Token {
-                token_type: TokenType::Symbol(symbol.clone()),
-                value: symbol.to_char().to_string(),
-                line,
-                column,
-            });
-            chars.next(); column += 1;
-        } else if ch.is_control() {
-            return Err(LemonError::new("Illegal control character", line, column));
-        } else if !ch.is_ascii_graphic() && !ch.is_whitespace() {
-            return Err(LemonError::new("Illegal character in input", line, column));
-        } else {
-            if current.is_empty() {
-                start_column = column;
+        let body = self.parse_block_statement(&[Keyword::While]);
+        self.consume(TokenType::Keyword(Keyword::While), &tr("E0201.do_while", &[]))?;
+        let condition = self.parse_condition()?;
+        while self.match_token(&[TokenType::Newline]) {} // `end` puede ir en la línea siguiente
+        self.consume_end("do ... while")?;
+
+        Ok(StatementKind::DoWhile { body: Box::new(body), condition })
+    }
+
+    /// `for init; cond; paso ... end` o `for item in lista ... end`
+    /// (el `for` ya fue consumido). En estilo C el encabezado va entre
+    /// paréntesis: `for (let i = 0; i < 3; i = i + 1) { ... }`.
+    fn parse_for(&mut self) -> Result<StatementKind, Diagnostic> {
+        let parenthesized = self.match_token(&[TokenType::Symbol(Symbol::OpenParen)]);
+        let close_header = |parser: &mut Self| -> Result<(), Diagnostic> {
+            if parenthesized {
+                parser.consume(TokenType::Symbol(Symbol::CloseParen), &tr("E0201.for_close_paren", &[]))?;
             }
-            current.push(ch);
-            chars.next(); column += 1;
+            Ok(())
+        };
+
+        if self.check(TokenType::Identifier)
+            && self.peek_next().token_type == TokenType::Keyword(Keyword::In)
+        {
+            let variable = self.advance().value.to_string();
+            self.advance(); // consume 'in'
+            let iterable = self.parse_expression()?;
+            close_header(self)?;
+            let body = self.parse_body("for")?;
+            return Ok(StatementKind::ForIn { variable, iterable, body: Box::new(body) });
         }
+
+        let init_start = self.peek().span;
+        let init = if self.match_token(&[TokenType::Keyword(Keyword::Let)]) {
+            self.parse_let()?
+        } else {
+            StatementKind::Expr(self.parse_expression()?)
+        };
+        let init = Statement::new(init, self.span_from(init_start));
+        self.consume(TokenType::Symbol(Symbol::Semicolon), &tr("E0201.for_init", &[]))?;
+        let condition = self.parse_expression()?;
+        self.consume(TokenType::Symbol(Symbol::Semicolon), &tr("E0201.for_condition", &[]))?;
+        let increment = self.parse_expression()?;
+        close_header(self)?;
+
+        let body = self.parse_body("for")?;
+
+        Ok(StatementKind::ForCStyle { init: Box::new(init), condition, increment, body: Box::new(body) })
     }
 
-    flush_current(&mut current, &mut tokens, line, start_column);
+    /// `try ... fail e ... end` o `try { ... } fail (e) { ... }` (el `try` ya
+    /// fue consumido). Tanto la rama `fail` como el nombre de la variable de
+    /// error son opcionales.
+    fn parse_try(&mut self) -> Result<StatementKind, Diagnostic> {
+        let braces = self.at_brace_block();
+        let body = if braces {
+            self.parse_brace_block()?
+        } else {
+            self.parse_block_statement(&[Keyword::Fail, Keyword::End])
+        };
 
-    tokens.push(Token {
-        token_type: TokenType::EOF,
-        value: "<EOF>".to_string(),
-        line,
-        column,
-    });
+        let fail = TokenType::Keyword(Keyword::Fail);
+        let has_handler = if braces { self.match_after_newlines(fail) } else { self.match_token(&[fail]) };
+        let (error, handler) = if has_handler {
+            let error = self.parse_fail_variable()?;
+            let handler = if braces {
+                self.parse_body("try")?
+            } else {
+                self.parse_block_statement(&[Keyword::End])
+            };
+            (error, Some(Box::new(handler)))
+        } else {
+            (None, None)
+        };
+        if !braces {
+            self.consume_end("try")?;
+        }
 
-    Ok(tokens)
-}
+        Ok(StatementKind::Try { body: Box::new(body), error, handler })
+    }
 
-fn flush_current(current: &mut String, tokens: &mut Vec<Token>, line: usize, column: usize) {
-    if current.is_empty() { return; }
-    tokens.push(classify(current, line, column));
-    current.clear();
-}
+    /// Nombre opcional de la variable de error después de `fail`: `e` o `(e)`.
+    fn parse_fail_variable(&mut self) -> Result<Option<String>, Diagnostic> {
+        if self.check(TokenType::Symbol(Symbol::OpenParen)) && self.peek_next().token_type == TokenType::Identifier {
+            self.advance(); // consume '('
+            let name = self.advance().value.to_string();
+            self.consume(TokenType::Symbol(Symbol::CloseParen), &tr("E0201.group_close_paren", &[]))?;
+            return Ok(Some(name));
+        }
+        if self.check(TokenType::Identifier) {
+            return Ok(Some(self.advance().value.to_string()));
+        }
+        Ok(None)
+    }
 
-fn classify(word: &str, line: usize, column: usize) -> Token {
-    use TokenType::*;
-    let value = word.to_string();
+    /// Condición de `if`, `elif`, `while` o `do ... while`. Los paréntesis
+    /// que la envuelven completa no forman parte del AST, así que
+    /// `if (x > 0)` y `if x > 0` producen lo mismo.
+    fn parse_condition(&mut self) -> Result<Expression, Diagnostic> {
+        let condition = self.parse_expression()?;
+        Ok(match condition.kind {
+            ExpressionKind::Grouping(inner) => *inner,
+            _ => condition,
+        })
+    }
 
-    if let Some(kw) = crate::grammar::keywords::Keyword::from_str(word) {
-        return Token { token_type: Keyword(kw), value, line, column };
+    /// `back valor` / `return valor`; el valor es opcional al final de un bloque.
+    fn parse_return(&mut self) -> Result<StatementKind, Diagnostic> {
+        if self.at_statement_boundary() {
+            return Ok(StatementKind::Return(None));
+        }
+        let value = self.parse_expression()?;
+        Ok(StatementKind::Return(Some(value)))
     }
-    if let Some(ty) = crate::grammar::types::Type::from_str(word) {
-        return Token { token_type: Type(ty), value, line, column };
+
+    // ========================
+    // Interfaz gráfica
+    // ========================
+
+    /// Elemento de la interfaz (la palabra clave ya fue consumida): un valor
+    /// opcional sin nombre (`wait 1000`) y atributos `nombre=valor`
+    /// (`btn text="Hola" when=saludar`). Los contenedores y eventos llevan
+    /// además un bloque, cuyo contenido también es parte de la interfaz.
+    fn parse_ui_element(&mut self, element: Keyword) -> Result<StatementKind, Diagnostic> {
+        let mut argument = None;
+        let mut attributes = Vec::new();
+        while !self.at_statement_boundary() && !self.check(TokenType::Symbol(Symbol::OpenBrace)) {
+            let named = matches!(self.peek().token_type, TokenType::Identifier | TokenType::Keyword(_) | TokenType::Type(_))
+                && self.peek_next().token_type == TokenType::Symbol(Symbol::Define);
+            if named {
+                let name = self.advance().value.to_string();
+                self.advance();
+                attributes.push((name, self.parse_expression()?));
+            } else if argument.is_none() && attributes.is_empty() {
+                argument = Some(self.parse_expression()?);
+            } else {
+                return Err(self.error_at_current(codes::UNEXPECTED_TOKEN, &tr("E0201.ui_attribute", &[])));
+            }
+        }
+
+        let body = if UI_CONTAINERS.contains(&element) {
+            let ui = std::mem::replace(&mut self.ui, true);
+            let body = self.parse_body(element.as_str());
+            self.ui = ui;
+            Some(Box::new(body?))
+        } else {
+            None
+        };
+        Ok(StatementKind::UiElement { element, argument, attributes, body })
     }
-    if let Some(op) = crate::grammar::operators::Operator::from_str(word) {
-        return Token { token_type: Operator(op), value, line, column };
+
+    /// Palabra clave contextual (ver `Keyword::is_contextual`) en el token
+    /// actual, que está al inicio de una instrucción. Dentro de una sección
+    /// `ui` siempre cuenta; fuera, solo si lo que sigue no continúa una
+    /// expresión, así `window = 1`, `box.valor` o `click(x)` usan el nombre.
+    fn contextual_keyword(&self) -> Option<Keyword> {
+        let element = Keyword::parse(&self.peek().value).filter(|kw| kw.is_contextual())?;
+        (self.ui || !continues_expression(self.peek_next().token_type)).then_some(element)
     }
-    if let Some(cmp) = crate::grammar::comparators::Comparator::from_str(word) {
-        return Token { token_type: Comparator(cmp), value, line, column };
+
+    // ========================
+    // Bloques
+    // ========================
+
+    /// Analiza instrucciones hasta encontrar (sin consumir) alguna de las
+    /// palabras clave `terminators`. Si se llega al final del archivo, el
+    /// `consume` del llamador reporta el `end` faltante.
+    ///
+    /// Una instrucción con errores se registra con `report` y se omite; el
+    /// resto del bloque se sigue analizando. Sin `terminators` analiza hasta EOF.
+    pub fn parse_block(&mut self, terminators: &[Keyword]) -> Vec<Statement> {
+        self.parse_statements(terminators, false)
     }
-    if let Some(log) = crate::grammar::logicals::Logical::from_str(word) {
-        return Token { token_type: Logical(log), value, line, column };
+
+    /// Cuerpo de `parse_block`; con `in_braces` también se detiene antes de `}`.
+    ///
+    /// Un `ui` vale hasta el final del bloque en que aparece.
+    fn parse_statements(&mut self, terminators: &[Keyword], in_braces: bool) -> Vec<Statement> {
+        let ui = self.ui;
+        let branch = std::mem::replace(&mut self.branch, terminators.contains(&Keyword::Else));
+        let mut statements = Vec::new();
+        loop {
+            // Las líneas vacías entre instrucciones no significan nada
+            while self.match_token(&[TokenType::Newline]) {}
+            if self.is_at_end()
+                || terminators.iter().any(|&kw| self.check(TokenType::Keyword(kw)))
+                || (in_braces && self.check(TokenType::Symbol(Symbol::CloseBrace)))
+            {
+                break;
+            }
+            let before = self.current;
+            match self.parse_statement() {
+                Ok(stmt) => statements.push(stmt),
+                Err(e) => {
+                    self.report(e);
+                    if self.current == before {
+                        self.advance(); // Asegura el avance aunque el error no consumiera nada
+                    }
+                    self.synchronize();
+                }
+            }
+        }
+        self.ui = ui;
+        self.branch = branch;
+        statements
     }
-    if let Some(comment) = crate::grammar::comments::Comment::from_str(word) {
-        return Token { token_type: Comment(comment), value, line, column };
+
+    /// Igual que `parse_block`, pero envuelve el resultado en un `Block`
+    /// cuyo rango va del primer al último token del bloque.
+    fn parse_block_statement(&mut self, terminators: &[Keyword]) -> Statement {
+        let start = self.peek().span;
+        let statements = self.parse_block(terminators);
+        let span = if statements.is_empty() { Span::point(start.start) } else { self.span_from(start) };
+        Statement::new(StatementKind::Block(statements), span)
     }
-    if word.starts_with(|c: char| c.is_ascii_digit()) {
-        return Token { token_type: Unknown, value, line, column };
+
+    /// Bloque entre llaves: `{ ... }`. El parser debe estar sobre la `{`.
+    fn parse_brace_block(&mut self) -> Result<Statement, Diagnostic> {
+        let start = self.peek().span;
+        let statements = self.parse_brace_statements()?;
+        Ok(Statement::new(StatementKind::Block(statements), self.span_from(start)))
     }
-    if word.chars().all(|c| c.is_alphanumeric() || c == '_') {
-        return Token { token_type: Identifier, value, line, column };
+
+    /// Instrucciones de un bloque `{ ... }`, consumiendo ambas llaves.
+    fn parse_brace_statements(&mut self) -> Result<Vec<Statement>, Diagnostic> {
+        self.consume(TokenType::Symbol(Symbol::OpenBrace), &tr("E0201.brace_open", &[]))?;
+        let statements = self.parse_statements(&[], true);
+        self.consume(TokenType::Symbol(Symbol::CloseBrace), &tr("E0201.brace_close", &[]))?;
+        Ok(statements)
     }
-    Token { token_type: Unknown, value, line, column }
-}
 
-fn try_double_char_operator(
-    chars: &mut std::iter::Peekable<std::str::Chars>,
-) -> Option<crate::grammar::operators::Operator> {
-    let ch1 = chars.peek().copied()?;
-    let ch2 = chars.clone().nth(1)?;
-    let pair = format!("{}{}", ch1, ch2);
+    /// Cuerpo de una estructura en cualquiera de los dos estilos: `{ ... }`
+    /// o instrucciones hasta el `end` que cierra `block`.
+    fn parse_body(&mut self, block: &str) -> Result<Statement, Diagnostic> {
+        if self.at_brace_block() {
+            return self.parse_brace_block();
+        }
+        let body = self.parse_block_statement(&[Keyword::End]);
+        self.consume_end(block)?;
+        Ok(body)
+    }
 
-    crate::grammar::operators::Operator::from_str(&pair).map(|op| {
-        chars.next(); chars.next(); op
-    })
-}
+    /// Indica si sigue un bloque `{`, aunque esté en la línea siguiente; en
+    /// ese caso deja el parser sobre la llave.
+    fn at_brace_block(&mut self) -> bool {
+        self.match_after_newlines_if(TokenType::Symbol(Symbol::OpenBrace), false)
+    }
 
-fn try_double_char_logical(
-    chars: &mut std::iter::Peekable<std::str::Chars>,
-) -> Option<crate::grammar::logicals::Logical> {
-    let ch1 = chars.peek().copied()?;
-    let ch2 = chars.clone().nth(1)?;
-    let pair = format!("{}{}", ch1, ch2);
+    /// Como `match_token`, pero salta los saltos de línea anteriores (para
+    /// `}` y `else` en líneas distintas). Si no coincide, no consume nada.
+    fn match_after_newlines(&mut self, token_type: TokenType) -> bool {
+        self.match_after_newlines_if(token_type, true)
+    }
 
-    crate::grammar::logicals::Logical::from_str(&pair).map(|log| {
-        chars.next(); chars.next(); log
-    })
-}
+    /// `else` o `elif` después de la `}` de un `if` en estilo C (ver `parse_brace_if`).
+    fn match_branch(&mut self, keyword: Keyword) -> bool {
+        if self.branch {
+            self.match_token(&[TokenType::Keyword(keyword)])
+        } else {
+            self.match_after_newlines(TokenType::Keyword(keyword))
+        }
+    }
 
-/// Punto de entrada externo para parser de expresiones
-pub fn parse_expression_tokens(tokens: &[Token]) -> Result<crate::ast::Expression, String> {
-    let mut parser = crate::parser::Parser::new(tokens.to_vec());
-    parser.parse_expression()
-}
+    fn match_after_newlines_if(&mut self, token_type: TokenType, consume: bool) -> bool {
+        let mut index = self.current;
+        while self.tokens[index].token_type == TokenType::Newline {
+            index += 1;
+            self.fill(index);
+        }
+        if self.tokens[index].token_type != token_type {
+            return false;
+        }
+        self.current = if consume { index + 1 } else { index };
+        self.fill(self.current + 1);
+        true
+    }
 
-/// Método de extensión para Parser
-impl crate::parser::Parser {
-    pub fn parse_expression(&mut self) -> Result<crate::ast::Expression, String> {
-        self.parse_assignment()
+    /// Indica si el token actual termina una instrucción (`;`, salto de línea,
+    /// fin de bloque o EOF).
+    fn at_statement_boundary(&self) -> bool {
+        self.is_at_end()
+            || self.check(TokenType::Symbol(Symbol::Semicolon))
+            || self.check(TokenType::Symbol(Symbol::CloseBrace))
+            || self.check(TokenType::Newline)
+            || BLOCK_BOUNDARIES.iter().any(|&kw| self.check(TokenType::Keyword(kw)))
     }
 }
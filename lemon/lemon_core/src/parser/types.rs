@@ -0,0 +1,54 @@
+use crate::ast::{TypeExpr, TypeExprKind};
+use crate::diagnostic::{codes, Diagnostic};
+use crate::grammar::*;
+use crate::i18n::tr;
+use crate::parser::Parser;
+use crate::token::TokenType;
+
+/// Métodos del parser dedicados a las anotaciones de tipo.
+///
+/// Gramática:
+///
+/// ```text
+/// tipo     := simple ("<" tipo ("," tipo)* ">")? "?"?
+/// simple   := int | float | string | bool | null | list | matrix | map | Nombre
+/// ```
+impl Parser {
+    /// Analiza una anotación de tipo: `int`, `Punto`, `list<int>`,
+    /// `map<string, float>` o `int?`.
+    pub fn parse_type(&mut self) -> Result<TypeExpr, Diagnostic> {
+        let start = self.peek().span;
+        let kind = match self.peek().token_type {
+            TokenType::Type(ty) => TypeExprKind::Builtin(ty),
+            TokenType::Identifier => TypeExprKind::Named(self.peek().value.to_string()),
+            _ => return Err(self.error_at_current(codes::UNEXPECTED_TOKEN, &tr("E0201.type_name", &[]))),
+        };
+        self.advance();
+        let mut ty = TypeExpr::new(kind, start);
+
+        if self.match_token(&[TokenType::Comparator(Comparator::Less)]) {
+            let mut args = Vec::new();
+            loop {
+                args.push(self.parse_type()?);
+                if !self.match_token(&[TokenType::Symbol(Symbol::Comma)]) {
+                    break;
+                }
+            }
+            self.consume(TokenType::Comparator(Comparator::Greater), &tr("E0201.type_args_close", &[]))?;
+            ty = TypeExpr::new(TypeExprKind::Generic { base: Box::new(ty), args }, self.span_from(start));
+        }
+
+        if self.match_token(&[TokenType::Symbol(Symbol::Question)]) {
+            ty = TypeExpr::new(TypeExprKind::Nullable(Box::new(ty)), self.span_from(start));
+        }
+        Ok(ty)
+    }
+
+    /// Anotación opcional después de `:`, como en `let x: int` o `(x: int)`.
+    pub(crate) fn parse_type_annotation(&mut self) -> Result<Option<TypeExpr>, Diagnostic> {
+        if self.match_token(&[TokenType::Symbol(Symbol::Colon)]) {
+            return self.parse_type().map(Some);
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,653 @@
+// ============================
+// resolve.rs — Resolución de nombres
+// ============================
+
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
+
+use crate::ast::{Deprecation, Expression, ExpressionKind, InterpolationPart, NodeId, Statement, StatementKind};
+use crate::diagnostic::{codes, Diagnostic};
+use crate::eval::builtins;
+use crate::i18n::tr;
+use crate::span::Span;
+
+// ============================
+// 📦 Ámbitos y declaraciones
+// ============================
+
+/// Identificador de una declaración: su posición en `Resolution::bindings`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub struct BindingId(pub usize);
+
+/// Identificador de un ámbito: su posición en `Resolution::scopes`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub struct ScopeId(pub usize);
+
+/// Qué instrucción declaró un nombre.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum BindingKind {
+    /// Función integrada del runtime (`print`, `len`, ...).
+    Builtin,
+    /// `let x = ...` o `let x`.
+    Variable,
+    /// `fn nombre(...)`.
+    Function,
+    /// Parámetro de una función.
+    Parameter,
+    /// Variable de `for x in ...`.
+    LoopVariable,
+    /// Variable que recibe el mensaje en `fail (e)`.
+    ErrorVariable,
+}
+
+/// Una declaración.
+#[derive(Debug, Clone, Serialize)]
+pub struct Binding {
+    /// Nombre declarado.
+    pub name: String,
+    /// Qué lo declaró.
+    pub kind: BindingKind,
+    /// Instrucción o parámetro que lo declara; `None` en las funciones integradas.
+    pub span: Option<Span>,
+    /// Ámbito al que pertenece.
+    pub scope: ScopeId,
+    /// Declarado con `sure`: no se puede reasignar ni borrar.
+    pub sure: bool,
+    /// Marcado como obsoleto con `old`: cada uso produce una advertencia.
+    pub deprecated: Option<Deprecation>,
+}
+
+/// Un ámbito: el de las funciones integradas, el programa, un bloque, una
+/// función (con sus parámetros), un bucle `for` o una rama `fail`. Son los
+/// mismos ámbitos que crea el evaluador.
+#[derive(Debug, Clone, Serialize)]
+pub struct Scope {
+    /// Ámbito que lo contiene; `None` solo en el de las funciones integradas.
+    pub parent: Option<ScopeId>,
+    /// Rango del código que cubre.
+    pub span: Span,
+    /// Declaraciones hechas directamente en este ámbito, en orden.
+    pub bindings: Vec<BindingId>,
+}
+
+/// Árbol de ámbitos de un programa, con cada uso de un nombre enlazado a su
+/// declaración.
+#[derive(Debug, Clone, Default)]
+pub struct Resolution {
+    /// Todos los ámbitos; `ScopeId(0)` es el de las funciones integradas y
+    /// `ScopeId(1)` el del programa.
+    pub scopes: Vec<Scope>,
+    /// Todas las declaraciones, en el orden en que aparecen.
+    pub bindings: Vec<Binding>,
+    /// Declaración a la que se refiere cada uso: una `Variable`, una
+    /// asignación (`x = ...`, `x += ...`) o un `del`, por su `NodeId`.
+    pub uses: HashMap<NodeId, BindingId>,
+    /// Declaración que hace cada `let`, `fn`, `for ... in`, `try` con
+    /// `fail (e)` o parámetro, por su `NodeId`.
+    pub declarations: HashMap<NodeId, BindingId>,
+    /// Rango y declaración de cada uso, en el orden en que se enlazaron.
+    sites: Vec<(Span, BindingId)>,
+    /// Nombres no definidos, declaraciones repetidas, nombres ocultos y usos
+    /// de declaraciones `old`.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Resolution {
+    /// Declaración con el identificador dado.
+    pub fn binding(&self, id: BindingId) -> &Binding {
+        &self.bindings[id.0]
+    }
+
+    /// Declaración a la que se refiere el uso `node`: una `Variable`, una
+    /// asignación o un `del`.
+    pub fn resolved(&self, node: NodeId) -> Option<BindingId> {
+        self.uses.get(&node).copied()
+    }
+
+    /// Declaración que hace la instrucción o el parámetro `node`.
+    pub fn declared(&self, node: NodeId) -> Option<BindingId> {
+        self.declarations.get(&node).copied()
+    }
+
+    /// Función integrada llamada `name`.
+    pub fn builtin(&self, name: &str) -> Option<BindingId> {
+        let scope = self.scopes.first()?;
+        scope.bindings.iter().copied().find(|&id| self.binding(id).name == name)
+    }
+
+    /// Rangos de todos los usos de una declaración, en orden.
+    pub fn uses_of(&self, id: BindingId) -> Vec<Span> {
+        let mut spans: Vec<Span> = self.sites.iter().filter(|&&(_, used)| used == id).map(|&(span, _)| span).collect();
+        spans.sort_by_key(|span| span.start);
+        spans
+    }
+}
+
+// ============================
+// 🔎 Resolutor
+// ============================
+
+/// Qué hace un uso con la variable.
+#[derive(Clone, Copy, PartialEq)]
+enum Access {
+    /// Lee su valor: `x`.
+    Read,
+    /// Le asigna un valor: `x = 1`.
+    Write,
+    /// Lee su valor y le asigna otro: `x += 1`.
+    Update,
+    /// La borra: `del x`.
+    Delete,
+}
+
+/// Uso que no se encontró al recorrerlo, dentro de código que se ejecuta más
+/// tarde (una función o un evento de la interfaz).
+struct Pending {
+    name: String,
+    node: NodeId,
+    span: Span,
+    access: Access,
+    /// Ámbito desde el que se vuelve a buscar al terminar.
+    from: ScopeId,
+}
+
+/// Llamada a una función con nombre fuera de código diferido: se ejecuta en
+/// cuanto el programa llega a ella.
+struct Call {
+    span: Span,
+    function: BindingId,
+    /// Ámbito donde está la llamada.
+    scope: ScopeId,
+}
+
+struct Resolver {
+    resolution: Resolution,
+    /// Nombres declarados hasta ahora en cada ámbito (por `ScopeId`); si un
+    /// nombre se repite, vale la última declaración.
+    names: Vec<HashMap<String, BindingId>>,
+    current: ScopeId,
+    /// Ámbito más interno que contiene código diferido. Desde ahí hacia
+    /// afuera, un nombre puede declararse después de usarse.
+    deferred: Option<ScopeId>,
+    pending: Vec<Pending>,
+    calls: Vec<Call>,
+}
+
+impl Resolver {
+    fn new(span: Span) -> Self {
+        let mut resolver = Resolver {
+            resolution: Resolution::default(),
+            names: Vec::new(),
+            current: ScopeId(0),
+            deferred: None,
+            pending: Vec::new(),
+            calls: Vec::new(),
+        };
+        resolver.enter(span);
+        // Las funciones integradas van en el ámbito más externo
+        for builtin in builtins() {
+            resolver.declare(builtin.name, BindingKind::Builtin, None);
+        }
+        resolver.enter(span);
+        resolver
+    }
+
+    // ========================
+    // Ámbitos
+    // ========================
+
+    /// Abre un ámbito hijo del actual y lo vuelve el actual.
+    fn enter(&mut self, span: Span) -> ScopeId {
+        let id = ScopeId(self.resolution.scopes.len());
+        let parent = (!self.resolution.scopes.is_empty()).then_some(self.current);
+        self.resolution.scopes.push(Scope { parent, span, bindings: Vec::new() });
+        self.names.push(HashMap::new());
+        std::mem::replace(&mut self.current, id)
+    }
+
+    /// Ejecuta `f` en un ámbito nuevo que cubre `span`.
+    fn scoped(&mut self, span: Span, f: impl FnOnce(&mut Self)) {
+        let previous = self.enter(span);
+        f(self);
+        self.current = previous;
+    }
+
+    /// Ejecuta `f` como código diferido: puede usar nombres que el ámbito
+    /// actual (o uno exterior) declara más abajo.
+    fn deferred(&mut self, f: impl FnOnce(&mut Self)) {
+        let previous = self.deferred.replace(self.current);
+        f(self);
+        self.deferred = previous;
+    }
+
+    /// Si `outer` es `scope` o lo contiene.
+    fn encloses(&self, outer: ScopeId, scope: ScopeId) -> bool {
+        let mut scope = Some(scope);
+        while let Some(id) = scope {
+            if id == outer {
+                return true;
+            }
+            scope = self.resolution.scopes[id.0].parent;
+        }
+        false
+    }
+
+    /// Busca `name` desde `scope` hacia afuera.
+    fn lookup(&self, scope: ScopeId, name: &str) -> Option<BindingId> {
+        let mut scope = Some(scope);
+        while let Some(id) = scope {
+            if let Some(&binding) = self.names[id.0].get(name) {
+                return Some(binding);
+            }
+            scope = self.resolution.scopes[id.0].parent;
+        }
+        None
+    }
+
+    // ========================
+    // Declaraciones y usos
+    // ========================
+
+    /// Declara `name` en el ámbito actual. Informa si ya estaba declarado en
+    /// el mismo ámbito (error) o en uno exterior (advertencia).
+    fn declare(&mut self, name: &str, kind: BindingKind, span: Option<Span>) -> BindingId {
+        let scope = self.current;
+        if let Some(span) = span {
+            if let Some(&previous) = self.names[scope.0].get(name) {
+                let mut diagnostic = Diagnostic::error(codes::DUPLICATE_DECLARATION, &tr(codes::DUPLICATE_DECLARATION, &[&name]))
+                    .with_span(span);
+                if kind == BindingKind::Variable {
+                    diagnostic = diagnostic.with_help(&tr("E0502.help", &[&name]));
+                }
+                if let Some(previous) = self.resolution.binding(previous).span {
+                    diagnostic = diagnostic.with_secondary(previous, &tr("E0502.previous", &[]));
+                }
+                self.resolution.diagnostics.push(diagnostic);
+            } else if let Some(parent) = self.resolution.scopes[scope.0].parent
+                && let Some(outer) = self.lookup(parent, name)
+                && let Some(outer) = self.resolution.binding(outer).span
+            {
+                // Ocultar una función integrada no se avisa: sus nombres son comunes
+                self.resolution.diagnostics.push(
+                    Diagnostic::warning(codes::SHADOWED_NAME, &tr(codes::SHADOWED_NAME, &[&name]))
+                        .with_span(span)
+                        .with_secondary(outer, &tr("E0503.outer", &[])),
+                );
+            }
+        }
+
+        let id = BindingId(self.resolution.bindings.len());
+        self.resolution.bindings.push(Binding { name: name.to_string(), kind, span, scope, sure: false, deprecated: None });
+        self.resolution.scopes[scope.0].bindings.push(id);
+        self.names[scope.0].insert(name.to_string(), id);
+        id
+    }
+
+    /// Enlaza el uso `node` de `name` con su declaración, o lo informa si no
+    /// existe.
+    fn use_name(&mut self, name: &str, node: NodeId, span: Span, access: Access) {
+        if let Some(id) = self.lookup(self.current, name) {
+            self.bind_use(node, span, id, access);
+        } else if let Some(from) = self.deferred {
+            self.pending.push(Pending { name: name.to_string(), node, span, access, from });
+        } else {
+            self.undefined(name, span, access, self.current);
+        }
+    }
+
+    /// Registra el uso `node` (en `span`) de la declaración `id`. Modificar o
+    /// borrar una variable `sure` es un error que señala su declaración; usar
+    /// una declaración `old`, una advertencia.
+    fn bind_use(&mut self, node: NodeId, span: Span, id: BindingId, access: Access) {
+        self.resolution.uses.insert(node, id);
+        self.resolution.sites.push((span, id));
+        let binding = self.resolution.binding(id);
+        if let Some(deprecation) = &binding.deprecated {
+            let mut diagnostic = Diagnostic::warning(codes::DEPRECATED_NAME, &tr(codes::DEPRECATED_NAME, &[&binding.name]))
+                .with_span(span)
+                .with_secondary(deprecation.span, &tr("E0505.declared", &[]));
+            if let Some(message) = &deprecation.message {
+                diagnostic = diagnostic.with_help(message);
+            }
+            self.resolution.diagnostics.push(diagnostic);
+        }
+
+        let binding = self.resolution.binding(id);
+        if !binding.sure || access == Access::Read {
+            return;
+        }
+        let key = if access == Access::Delete { "E0504.delete" } else { codes::SURE_BINDING };
+        let mut diagnostic = Diagnostic::error(codes::SURE_BINDING, &tr(key, &[&binding.name]))
+            .with_span(span)
+            .with_help(&tr("E0504.help", &[]));
+        if let Some(declared) = binding.span {
+            diagnostic = diagnostic.with_secondary(declared, &tr("E0504.declared", &[]));
+        }
+        self.resolution.diagnostics.push(diagnostic);
+    }
+
+    fn undefined(&mut self, name: &str, span: Span, access: Access, scope: ScopeId) {
+        let key = match access {
+            Access::Read | Access::Update => codes::UNDEFINED_NAME,
+            Access::Write => "E0501.assign",
+            Access::Delete => "E0501.delete",
+        };
+        let mut diagnostic = Diagnostic::error(codes::UNDEFINED_NAME, &tr(key, &[&name])).with_span(span);
+        if let Some(suggestion) = self.closest(scope, name) {
+            diagnostic = diagnostic.with_help(&tr("E0501.help", &[&suggestion]));
+        }
+        self.resolution.diagnostics.push(diagnostic);
+    }
+
+    /// Nombre visible desde `scope` que más se parece a `name`, si hay uno
+    /// lo bastante cercano (como `contador` para `contdor`). Los nombres de
+    /// una o dos letras no reciben sugerencias: casi todo se les parece.
+    fn closest(&self, scope: ScopeId, name: &str) -> Option<String> {
+        let limit = name.chars().count() / 3;
+        let mut best: Option<(usize, &str)> = None;
+        let mut scope = Some(scope);
+        while let Some(id) = scope {
+            for candidate in self.names[id.0].keys() {
+                let distance = edit_distance(name, candidate);
+                if distance <= limit && best.is_none_or(|(d, b)| (distance, candidate.as_str()) < (d, b)) {
+                    best = Some((distance, candidate));
+                }
+            }
+            scope = self.resolution.scopes[id.0].parent;
+        }
+        best.map(|(_, candidate)| candidate.to_string())
+    }
+
+    /// Vuelve a buscar los usos diferidos, ahora con todas las declaraciones.
+    fn finish(mut self) -> Resolution {
+        for pending in std::mem::take(&mut self.pending) {
+            match self.lookup(pending.from, &pending.name) {
+                Some(id) => self.bind_use(pending.node, pending.span, id, pending.access),
+                None => self.undefined(&pending.name, pending.span, pending.access, pending.from),
+            }
+        }
+        self.early_calls();
+        self.resolution
+    }
+
+    /// Avisa de las llamadas que están antes de que se declare algo que la
+    /// función usa, directamente o a través de otra función: con
+    /// `fn total() back iva end`, llamar a `total()` antes de `let iva` falla
+    /// al ejecutar. Es una advertencia porque no se sabe si la llamada se
+    /// ejecuta (puede estar en un `if` que nunca se cumple).
+    fn early_calls(&mut self) {
+        let mut sites = self.resolution.sites.clone();
+        sites.sort_by_key(|(span, _)| span.start);
+        'calls: for call in std::mem::take(&mut self.calls) {
+            let mut functions = vec![call.function];
+            let mut seen = HashSet::from([call.function]);
+            while let Some(function) = functions.pop() {
+                let Some(body) = self.resolution.binding(function).span else { continue };
+                let first = sites.partition_point(|(span, _)| span.start < body.start);
+                for &(used, id) in sites[first..].iter().take_while(|(span, _)| span.start < body.end) {
+                    let binding = self.resolution.binding(id);
+                    // Las declaraciones de la propia función existen cuando se las usa
+                    let Some(declared) = binding.span.filter(|d| d.start < body.start || d.end > body.end) else { continue };
+                    let sequential = matches!(binding.kind, BindingKind::Variable | BindingKind::Function);
+                    if sequential && declared.end > call.span.start && self.encloses(binding.scope, call.scope) {
+                        let called = &self.resolution.binding(call.function).name;
+                        let diagnostic = Diagnostic::warning(codes::CALL_BEFORE_DECLARATION, &tr(codes::CALL_BEFORE_DECLARATION, &[called, &binding.name]))
+                            .with_span(call.span)
+                            .with_secondary(used, &tr("E0506.used", &[]))
+                            .with_secondary(declared, &tr("E0506.declared", &[]))
+                            .with_help(&tr("E0506.help", &[]));
+                        self.resolution.diagnostics.push(diagnostic);
+                        continue 'calls;
+                    }
+                    if binding.kind == BindingKind::Function && seen.insert(id) {
+                        functions.push(id);
+                    }
+                }
+            }
+        }
+    }
+
+    // ========================
+    // Instrucciones
+    // ========================
+
+    fn block(&mut self, statements: &[Statement]) {
+        for stmt in statements {
+            self.statement(stmt);
+        }
+    }
+
+    fn statement(&mut self, stmt: &Statement) {
+        match &stmt.kind {
+            // El valor se resuelve antes de declarar: en `let x = x + 1` el
+            // `x` de la derecha es el anterior
+            StatementKind::Let { name, sure, deprecated, value, .. } => {
+                self.expression(value);
+                let id = self.declare(name, BindingKind::Variable, Some(stmt.span));
+                self.resolution.declarations.insert(stmt.id, id);
+                self.resolution.bindings[id.0].sure = *sure;
+                self.resolution.bindings[id.0].deprecated = deprecated.clone();
+            }
+            StatementKind::Variable { name, initializer, deprecated, .. } => {
+                if let Some(initializer) = initializer {
+                    self.expression(initializer);
+                }
+                let id = self.declare(name, BindingKind::Variable, Some(stmt.span));
+                self.resolution.declarations.insert(stmt.id, id);
+                self.resolution.bindings[id.0].deprecated = deprecated.clone();
+            }
+            StatementKind::Delete(name) => self.use_name(name, stmt.id, stmt.span, Access::Delete),
+
+            StatementKind::Function { name, params, deprecated, body, .. } => {
+                if let Some(name) = name {
+                    let id = self.declare(name, BindingKind::Function, Some(stmt.span));
+                    self.resolution.declarations.insert(stmt.id, id);
+                    self.resolution.bindings[id.0].deprecated = deprecated.clone();
+                }
+                self.deferred(|resolver| {
+                    resolver.scoped(stmt.span, |resolver| {
+                        for param in params {
+                            let id = resolver.declare(&param.name, BindingKind::Parameter, Some(param.span));
+                            resolver.resolution.declarations.insert(param.id, id);
+                        }
+                        resolver.block(body);
+                    });
+                });
+            }
+
+            StatementKind::If { condition, then_branch, else_branch } => {
+                self.expression(condition);
+                self.statement(then_branch);
+                if let Some(else_branch) = else_branch {
+                    self.statement(else_branch);
+                }
+            }
+
+            StatementKind::While { condition, body } | StatementKind::DoWhile { body, condition } => {
+                self.expression(condition);
+                self.statement(body);
+            }
+
+            StatementKind::ForCStyle { init, condition, increment, body } => self.scoped(stmt.span, |resolver| {
+                resolver.statement(init);
+                resolver.expression(condition);
+                resolver.statement(body);
+                resolver.expression(increment);
+            }),
+
+            StatementKind::ForIn { variable, iterable, body } => {
+                self.expression(iterable);
+                self.scoped(stmt.span, |resolver| {
+                    let id = resolver.declare(variable, BindingKind::LoopVariable, Some(stmt.span));
+                    resolver.resolution.declarations.insert(stmt.id, id);
+                    resolver.statement(body);
+                });
+            }
+
+            StatementKind::Return(value) => {
+                if let Some(value) = value {
+                    self.expression(value);
+                }
+            }
+
+            StatementKind::Expr(expr) | StatementKind::Expression(expr) => self.expression(expr),
+            StatementKind::BuiltinCall(_, args) => {
+                for arg in args {
+                    self.expression(arg);
+                }
+            }
+
+            StatementKind::Block(statements) => self.scoped(stmt.span, |resolver| resolver.block(statements)),
+
+            StatementKind::Try { body, error, handler } => {
+                self.statement(body);
+                if let Some(handler) = handler {
+                    self.scoped(handler.span, |resolver| {
+                        if let Some(name) = error {
+                            let id = resolver.declare(name, BindingKind::ErrorVariable, Some(stmt.span));
+                            resolver.resolution.declarations.insert(stmt.id, id);
+                        }
+                        resolver.statement(handler);
+                    });
+                }
+            }
+
+            // Los eventos (`when=mi_evento`) se ejecutan cuando la interfaz ya está armada
+            StatementKind::UiElement { argument, attributes, body, .. } => self.deferred(|resolver| {
+                for expr in argument.iter().chain(attributes.iter().map(|(_, value)| value)) {
+                    resolver.expression(expr);
+                }
+                if let Some(body) = body {
+                    resolver.statement(body);
+                }
+            }),
+
+            StatementKind::Break | StatementKind::Continue | StatementKind::End | StatementKind::Ui => {}
+        }
+    }
+
+    // ========================
+    // Expresiones
+    // ========================
+
+    fn expression(&mut self, expr: &Expression) {
+        match &expr.kind {
+            ExpressionKind::Variable(name) => self.use_name(name, expr.id, expr.span, Access::Read),
+            ExpressionKind::Assign { variable, value } => {
+                self.expression(value);
+                self.use_name(variable, expr.id, expr.span, Access::Write);
+            }
+            ExpressionKind::CompoundAssign { target, value, .. } => {
+                self.expression(value);
+                match &target.kind {
+                    ExpressionKind::Variable(variable) => self.use_name(variable, expr.id, expr.span, Access::Update),
+                    _ => self.expression(target),
+                }
+            }
+
+            ExpressionKind::Interpolated(parts) => {
+                for part in parts {
+                    if let InterpolationPart::Expression(expr) = part {
+                        self.expression(expr);
+                    }
+                }
+            }
+            ExpressionKind::Binary { left, right, .. } => {
+                self.expression(left);
+                self.expression(right);
+            }
+            ExpressionKind::Unary { expr, .. } | ExpressionKind::Grouping(expr) => self.expression(expr),
+            ExpressionKind::Call { function, args } => {
+                self.expression(function);
+                for arg in args {
+                    self.expression(arg);
+                }
+                if self.deferred.is_none()
+                    && let ExpressionKind::Variable(_) = function.kind
+                    && let Some(id) = self.resolution.resolved(function.id)
+                    && self.resolution.binding(id).kind == BindingKind::Function
+                {
+                    self.calls.push(Call { span: expr.span, function: id, scope: self.current });
+                }
+            }
+            ExpressionKind::Get { object, .. } => self.expression(object),
+            ExpressionKind::Set { object, value, .. } => {
+                self.expression(object);
+                self.expression(value);
+            }
+            ExpressionKind::Ternary { condition, then_branch, else_branch } => {
+                self.expression(condition);
+                self.expression(then_branch);
+                self.expression(else_branch);
+            }
+
+            ExpressionKind::Integer(_)
+            | ExpressionKind::Float(_)
+            | ExpressionKind::String(_)
+            | ExpressionKind::Boolean(_)
+            | ExpressionKind::Literal(_)
+            | ExpressionKind::None => {}
+        }
+    }
+}
+
+/// Cantidad mínima de caracteres que hay que insertar, borrar o cambiar
+/// para pasar de `a` a `b` (distancia de Levenshtein).
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = (above + 1).min(row[j] + 1).min(diagonal + usize::from(ca != cb));
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+// ============================
+// 🚪 Punto de entrada
+// ============================
+
+/// Arma el árbol de ámbitos del programa y enlaza cada uso de un nombre con
+/// su declaración.
+///
+/// El código de una función puede usar nombres declarados más abajo (se
+/// ejecuta cuando se la llama); el resto, solo los declarados antes. Una
+/// llamada escrita antes de esas declaraciones genera una advertencia.
+///
+/// ```
+/// # use lemon_core::resolve::{resolve, BindingKind};
+/// let statements = lemon_core::parse("let contador = 1\nprint(contdor)\n").unwrap();
+/// let resolution = resolve(&statements);
+/// assert_eq!(resolution.diagnostics[0].code, "E0501");
+/// assert_eq!(resolution.diagnostics[0].help.as_deref(), Some("¿quisiste decir 'contador'?"));
+///
+/// let statements = lemon_core::parse("fn par(n)\n  back n % 2 == 0\nend\npar(4)\n").unwrap();
+/// let resolution = resolve(&statements);
+/// assert!(resolution.diagnostics.is_empty());
+/// let uses = resolution.bindings.iter().position(|b| b.kind == BindingKind::Parameter).unwrap();
+/// assert_eq!(resolution.uses_of(lemon_core::resolve::BindingId(uses)).len(), 1);
+///
+/// let statements = lemon_core::parse("old(\"usa nueva\") fn vieja()\nend\nvieja()\n").unwrap();
+/// let resolution = resolve(&statements);
+/// assert_eq!(resolution.diagnostics[0].code, "E0505");
+/// assert_eq!(resolution.diagnostics[0].help.as_deref(), Some("usa nueva"));
+///
+/// let statements = lemon_core::parse("fn total(p)\n  back p * iva\nend\nprint(total(100))\nlet iva = 0.21\n").unwrap();
+/// let resolution = resolve(&statements);
+/// assert_eq!(resolution.diagnostics[0].code, "E0506");
+/// ```
+pub fn resolve(program: &[Statement]) -> Resolution {
+    let span = match (program.first(), program.last()) {
+        (Some(first), Some(last)) => first.span.to(last.span),
+        _ => Span::default(),
+    };
+    let mut resolver = Resolver::new(span);
+    resolver.block(program);
+    resolver.finish()
+}
@@ -0,0 +1,130 @@
+// ============================
+// span.rs — Ubicaciones en el código fuente
+// ============================
+
+use serde::{Serialize, Deserialize};
+use std::fmt;
+use unicode_segmentation::UnicodeSegmentation;
+
+// ============================
+// 📏 Rango de bytes
+// ============================
+
+/// Rango de bytes `[start, end)` dentro del código fuente.
+///
+/// Todos los tokens y nodos del AST llevan un `Span`, de modo que los
+/// diagnósticos, editores y depuradores puedan señalar el texto exacto.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct Span {
+    pub start: usize, // Primer byte (incluido)
+    pub end: usize,   // Último byte (excluido)
+}
+
+impl Span {
+    /// Crea un rango a partir de sus posiciones en bytes.
+    pub fn new(start: usize, end: usize) -> Self {
+        Span { start, end }
+    }
+
+    /// Rango vacío en una posición (útil para EOF o inserciones sugeridas).
+    pub fn point(offset: usize) -> Self {
+        Span { start: offset, end: offset }
+    }
+
+    /// Une dos rangos: desde el inicio de `self` hasta el final de `other`.
+    pub fn to(self, other: Span) -> Span {
+        Span { start: self.start.min(other.start), end: self.end.max(other.end) }
+    }
+
+    /// Cantidad de bytes cubiertos.
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Indica si el rango no cubre ningún byte.
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+// ============================
+// 🗺️ Mapa de fuente
+// ============================
+
+/// Línea y columna (ambas desde 1). La columna se cuenta en grupos de
+/// grafemas (`ñ` es una columna aunque esté escrita con una marca
+/// combinante), igual que en los tokens del lexer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Traduce rangos de bytes a archivo/línea/columna para un archivo fuente.
+///
+/// Guarda el inicio de cada línea, así que funciona igual para tokens que
+/// abarcan varias líneas (cadenas multilínea, comentarios en bloque).
+#[derive(Debug, Clone)]
+pub struct SourceMap {
+    name: String,
+    source: String,
+    line_starts: Vec<usize>, // Byte donde empieza cada línea
+}
+
+impl SourceMap {
+    /// Construye el mapa para el archivo `name` con contenido `source`.
+    pub fn new(name: &str, source: &str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        SourceMap { name: name.to_string(), source: source.to_string(), line_starts }
+    }
+
+    /// Nombre del archivo.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Código fuente completo.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Línea y columna de un byte. Las posiciones fuera del archivo se ajustan al final.
+    pub fn location(&self, offset: usize) -> Location {
+        let offset = offset.min(self.source.len());
+        let line_index = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let line_start = self.line_starts[line_index];
+        let column = self.source[line_start..offset].graphemes(true).count() + 1;
+        Location { line: line_index + 1, column }
+    }
+
+    /// Posición de inicio y de fin de un rango.
+    pub fn span_locations(&self, span: Span) -> (Location, Location) {
+        (self.location(span.start), self.location(span.end))
+    }
+
+    /// Texto de una línea (desde 1), sin el salto de línea final.
+    pub fn line_text(&self, line: usize) -> &str {
+        let start = self.line_starts[line - 1];
+        let end = self.line_starts.get(line).copied().unwrap_or(self.source.len());
+        self.source[start..end].trim_end_matches(['\n', '\r'])
+    }
+
+    /// Cantidad de líneas del archivo.
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Formatea el inicio de un rango como `archivo:línea:columna`.
+    pub fn describe(&self, span: Span) -> String {
+        let start = self.location(span.start);
+        format!("{}:{}:{}", self.name, start.line, start.column)
+    }
+}
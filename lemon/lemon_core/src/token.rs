@@ -3,8 +3,13 @@
 // ============================
 
 use crate::grammar::*; // Importa enums de palabras clave, operadores, símbolos, etc.
+use crate::span::Span; // Rango de bytes en el código fuente
+use std::collections::HashSet;
+use std::hash::{BuildHasherDefault, Hasher};
 use std::fmt;          // Para implementar fmt::Display
-use serde::{Serialize, Deserialize}; // (opcional) Para serializar a JSON o similar
+use std::ops::Deref;
+use std::rc::Rc;
+use serde::{Serialize, Deserialize, Serializer, Deserializer}; // (opcional) Para serializar a JSON o similar
 
 // ============================
 // 🔠 Tipos de token reconocidos
@@ -13,7 +18,8 @@ use serde::{Serialize, Deserialize}; // (opcional) Para serializar a JSON o simi
 #[derive(Debug, Clone, PartialEq, Copy, Serialize, Deserialize)]
 pub enum TokenType {
     Identifier,                     // Identificadores de variables, funciones, etc.
-    Number,                         // Números (int o float, se decide en el parser)
+    Integer,                        // Enteros: 42, 0xFF, 0b1010, 0o17, 1_000
+    Float,                          // Decimales: 3.14, 6.02e23, 1e-3
     Keyword(Keyword),               // Palabras clave como let, if, while...
     Symbol(Symbol),                 // Símbolos como (, ), {, }, =, ;, etc.
     Unknown,                        // Token no reconocido (error léxico)
@@ -23,6 +29,10 @@ pub enum TokenType {
     Type(Type),                     // Tipos de dato: string, bool, number, etc.
     Comment(Comment),               // Comentarios: //, /* */
     StringLiteral,                  // Cadenas de texto: "hola mundo"
+    Newline,                        // Salto de línea que termina una instrucción
+    InterpolationStart,             // Comilla de apertura de una cadena con interpolaciones: "Hola, {nombre}"
+    StringFragment,                 // Texto literal entre interpolaciones
+    InterpolationEnd,               // Comilla de cierre de una cadena con interpolaciones
     EOF,                            // Fin del archivo fuente
 }
 
@@ -33,9 +43,231 @@ pub enum TokenType {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Token {
     pub token_type: TokenType,  // El tipo de token (Keyword, Operator, etc.)
-    pub value: String,          // Texto exacto del código fuente
+    pub value: Lexeme,          // Texto exacto del código fuente
     pub line: usize,            // Línea en el código fuente
-    pub column: usize,          // Columna (posición horizontal)
+    pub column: usize,          // Columna en grafemas (posición horizontal)
+    pub span: Span,             // Rango exacto de bytes en el código fuente
+}
+
+// ============================
+// 🔤 Texto de los tokens
+// ============================
+
+/// Texto de un token. Se usa como un `&str` (implementa `Deref<Target = str>`).
+///
+/// La puntuación y el `EOF` apuntan a texto estático, y las palabras y
+/// números se comparten entre todos los tokens iguales (ver `Interner`): un
+/// archivo con mil `contador` guarda el texto una sola vez, y clonar un token
+/// nunca copia su texto.
+///
+/// ```
+/// # use lemon_core::token::Lexeme;
+/// let a = Lexeme::from("x");
+/// assert_eq!(a, "x");
+/// assert_eq!(a.len(), 1);
+/// assert_eq!(format!("{:?}", Lexeme::from_static("+=")), "\"+=\"");
+/// ```
+#[derive(Clone)]
+pub struct Lexeme(LexemeText);
+
+#[derive(Clone)]
+enum LexemeText {
+    Static(&'static str),
+    Shared(Rc<str>),
+}
+
+impl Lexeme {
+    /// Texto estático, sin reservar memoria.
+    pub const fn from_static(text: &'static str) -> Self {
+        Lexeme(LexemeText::Static(text))
+    }
+
+    /// El texto como `&str`.
+    pub fn as_str(&self) -> &str {
+        match &self.0 {
+            LexemeText::Static(text) => text,
+            LexemeText::Shared(text) => text,
+        }
+    }
+}
+
+impl Deref for Lexeme {
+    type Target = str;
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl From<&str> for Lexeme {
+    fn from(text: &str) -> Self {
+        Lexeme(LexemeText::Shared(Rc::from(text)))
+    }
+}
+
+impl From<String> for Lexeme {
+    fn from(text: String) -> Self {
+        Lexeme(LexemeText::Shared(Rc::from(text)))
+    }
+}
+
+impl PartialEq for Lexeme {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl PartialEq<str> for Lexeme {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Lexeme {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl fmt::Debug for Lexeme {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for Lexeme {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for Lexeme {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Lexeme {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Lexeme::from)
+    }
+}
+
+/// Tabla de textos ya vistos: pedir dos veces el mismo texto devuelve el
+/// mismo `Lexeme`, sin reservar memoria la segunda vez.
+///
+/// ```
+/// # use lemon_core::token::Interner;
+/// let mut interner = Interner::default();
+/// let a = interner.intern("contador");
+/// let b = interner.intern("contador");
+/// assert_eq!(a, b);
+/// assert_eq!(interner.len(), 1);
+/// ```
+#[derive(Default)]
+pub struct Interner {
+    texts: HashSet<Rc<str>, BuildHasherDefault<Fnv1a>>,
+}
+
+impl Interner {
+    /// `Lexeme` compartido para `text`.
+    pub fn intern(&mut self, text: &str) -> Lexeme {
+        if let Some(shared) = self.texts.get(text) {
+            return Lexeme(LexemeText::Shared(Rc::clone(shared)));
+        }
+        let shared: Rc<str> = Rc::from(text);
+        self.texts.insert(Rc::clone(&shared));
+        Lexeme(LexemeText::Shared(shared))
+    }
+
+    /// Cantidad de textos distintos guardados.
+    pub fn len(&self) -> usize {
+        self.texts.len()
+    }
+
+    /// Indica si todavía no se guardó ningún texto.
+    pub fn is_empty(&self) -> bool {
+        self.texts.is_empty()
+    }
+}
+
+/// Hash FNV-1a: para textos cortos como los nombres de un programa es
+/// bastante más rápido que el hash por defecto de `HashSet`, y el lexer no
+/// necesita protección contra colisiones provocadas.
+#[derive(Default)]
+struct Fnv1a(u64);
+
+impl Hasher for Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut hash = if self.0 == 0 { 0xcbf2_9ce4_8422_2325 } else { self.0 };
+        for &byte in bytes {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+        self.0 = hash;
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+// ============================
+// 🧹 Trivia: lo que el parser ignora
+// ============================
+
+/// Clase de trivia.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TriviaKind {
+    Whitespace,                     // Espacios y tabulaciones
+    Newline,                        // Salto de línea que no termina una instrucción
+    Comment(Comment),               // Comentario de línea o de bloque
+}
+
+/// Texto entre tokens que no cambia el significado del programa.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub text: String,           // Texto exacto, sin cambios
+    pub span: Span,
+}
+
+/// Token del modo sin pérdida (`lexer::tokenize_lossless`): además del token
+/// guarda su texto original y la trivia que lo rodea, así que concatenar
+/// todo reproduce el archivo byte a byte.
+///
+/// La trivia final de un token llega hasta el primer salto de línea
+/// (incluido); el resto pertenece a la trivia inicial del siguiente token.
+/// Así un comentario al final de una línea queda con el token de esa línea.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LosslessToken {
+    pub token: Token,
+    pub text: String,           // Texto exacto del token en el código fuente
+    pub leading: Vec<Trivia>,   // Trivia antes del token
+    pub trailing: Vec<Trivia>,  // Trivia después del token, hasta el fin de línea
+}
+
+impl LosslessToken {
+    /// Texto completo: trivia inicial, token y trivia final.
+    pub fn full_text(&self) -> String {
+        let mut out = String::new();
+        self.write_to(&mut out);
+        out
+    }
+
+    fn write_to(&self, out: &mut String) {
+        self.leading.iter().for_each(|trivia| out.push_str(&trivia.text));
+        out.push_str(&self.text);
+        self.trailing.iter().for_each(|trivia| out.push_str(&trivia.text));
+    }
+}
+
+/// Reconstruye el código fuente original a partir de tokens sin pérdida.
+pub fn to_source(tokens: &[LosslessToken]) -> String {
+    let mut out = String::new();
+    for token in tokens {
+        token.write_to(&mut out);
+    }
+    out
 }
 
 // ============================